@@ -7,7 +7,9 @@ use clap::{App, Arg};
 use harbor_rs::Client;
 use serde::{Deserialize, Serialize};
 use harbor_rs::harbor::common::parse_time;
-use harbor_rs::harbor::gc::{Schedule, ScheduleType};
+use harbor_rs::harbor::job::JobStatus;
+use harbor_rs::harbor::reference::ImageRef;
+use harbor_rs::harbor::tag::ImmutableTagError;
 
 extern crate base64;
 
@@ -17,6 +19,7 @@ struct Config {
     pub harbor_username: String,
     pub harbor_password: String,
     pub clean_interval: u32,
+    /// The repositories, e.g. `team/app` or `harbor.example.com/team/app`.
     pub repos: Vec<String>,
 }
 
@@ -39,6 +42,7 @@ async fn main() {
     let client = harbor_rs::Client::new(config.harbor_base_api, config.harbor_username, config.harbor_password).unwrap();
     let clean_interval = Local::now().sub(Duration::days(config.clean_interval as i64));
     for repo in config.repos {
+        let repo = parse_repo(&client, &repo).unwrap();
         clean(&client, repo.as_str(), clean_interval).await.unwrap();
     }
 }
@@ -51,39 +55,43 @@ fn load_config(path: &str) -> Result<Config> {
     Ok(config)
 }
 
+/// Parse the repository name from the repository or image reference in the config.
+fn parse_repo(client: &Client, repo: &str) -> Result<String> {
+    let image: ImageRef = repo.parse()?;
+    client.check_image_ref(&image)?;
+    if image.tag.is_some() || image.digest.is_some() {
+        return Err(anyhow!("repository {} must not have a tag or digest", repo));
+    }
+    Ok(image.repo_name())
+}
+
 async fn clean(client: &Client, repo: &str, interval: DateTime<Local>) -> Result<()> {
     let tags = client.list_tags(repo, None, Some(true)).await?;
     for tag in tags {
         let push_time = parse_time(tag.push_time.as_str())?;
         if push_time.le(&interval) {
-            client.delete_tag(repo, tag.name.as_str()).await?;
+            if let Err(e) = client.delete_tag(repo, tag.name.as_str()).await {
+                if e.downcast_ref::<ImmutableTagError>().is_none() {
+                    return Err(e);
+                }
+                println!("skipped {} which is immutable", tag.name);
+                continue;
+            }
             println!("deleted {} which pushed at {}", tag.name, push_time.format("%Y-%m-%d %H:%M:%S"));
         }
     }
-    Ok(manual_gc(client).await?)
+    manual_gc(client).await
 }
 
 async fn manual_gc(client: &Client) -> Result<()> {
-    let schedule = Schedule {
-        schedule_type: ScheduleType::Manual.to_string(),
-        cron: None,
-    };
-    client.create_schedule(&schedule).await?;
-    println!("manual gc schedule created");
+    let job = client.trigger_gc(None).await?;
+    println!("manual gc {} triggered", job.id());
 
-    for _ in 1..100 {
-        let gc_results = client.list_gc_results().await?;
-        if gc_results.len() == 0 {
-            return Err(anyhow!("gc results is empty"));
-        }
-        let gc_result = gc_results.get(0).unwrap();
-        if gc_result.job_status == "finished" {
-            println!("gc status: finished");
-            return Ok(());
-        }
-        println!("gc status: {}", gc_result.job_status);
-        std::thread::sleep(std::time::Duration::from_secs(1));
+    let status = job.wait(std::time::Duration::from_secs(600)).await
+        .map_err(|e| anyhow!("{}, please check manually", e))?;
+    println!("gc status: {:?}", status);
+    if status != JobStatus::Succeeded {
+        return Err(anyhow!("gc is not succeeded, please check manually"));
     }
-    Err(anyhow!("gc is still not finished, please check manually"))
+    Ok(())
 }
-
@@ -0,0 +1,87 @@
+use std::io::Read;
+use anyhow::Result;
+use clap::{App, Arg};
+use serde::{Deserialize, Serialize};
+use harbor_rs::harbor::provision::{load_desired_users_csv, load_desired_users_yaml, ActionOutcome, DesiredUser, ReconcileOptions};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Config {
+    pub harbor_base_api: String,
+    pub harbor_username: String,
+    pub harbor_password: String,
+}
+
+#[tokio::main]
+async fn main() {
+    let matches = App::new("provision-users")
+        .version("1.0")
+        .author("K8sCat <rustpanic@gmail.com>")
+        .about("Provision harbor users")
+        .arg(Arg::with_name("config")
+            .short("c")
+            .long("config")
+            .value_name("FILE")
+            .default_value("config.yml")
+            .help("Set config file")
+            .takes_value(true))
+        .arg(Arg::with_name("users")
+            .short("u")
+            .long("users")
+            .value_name("FILE")
+            .required(true)
+            .help("Set desired users file, in YAML or CSV")
+            .takes_value(true))
+        .arg(Arg::with_name("dry-run")
+            .long("dry-run")
+            .help("Only print the changes"))
+        .arg(Arg::with_name("delete")
+            .long("delete")
+            .help("Delete the users not in the desired users file"))
+        .get_matches();
+    let config = load_config(matches.value_of("config").unwrap()).unwrap();
+    let users = load_users(matches.value_of("users").unwrap()).unwrap();
+    let client = harbor_rs::Client::new(config.harbor_base_api, config.harbor_username, config.harbor_password).unwrap();
+    let options = ReconcileOptions {
+        dry_run: matches.is_present("dry-run"),
+        delete_missing: matches.is_present("delete"),
+    };
+    let results = client.reconcile_users(&users, &options).await.unwrap();
+    if results.is_empty() {
+        println!("users are up to date");
+    }
+    let mut failed = 0;
+    for result in results {
+        match result.outcome {
+            ActionOutcome::Planned => println!("[dry-run] {}", result.action),
+            ActionOutcome::Applied => println!("[ok] {}", result.action),
+            ActionOutcome::Failed(e) => {
+                failed += 1;
+                println!("[failed] {}: {}", result.action, e);
+            }
+        }
+    }
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn read_file(path: &str) -> Result<String> {
+    let mut f = std::fs::File::open(path)?;
+    let mut s = String::new();
+    f.read_to_string(&mut s)?;
+    Ok(s)
+}
+
+fn load_config(path: &str) -> Result<Config> {
+    let config: Config = serde_yaml::from_str(&read_file(path)?)?;
+    Ok(config)
+}
+
+fn load_users(path: &str) -> Result<Vec<DesiredUser>> {
+    let s = read_file(path)?;
+    if path.ends_with(".csv") {
+        load_desired_users_csv(&s)
+    } else {
+        load_desired_users_yaml(&s)
+    }
+}
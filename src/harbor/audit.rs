@@ -0,0 +1,227 @@
+use std::fmt;
+use super::client::Client;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use anyhow::{anyhow, Result};
+use reqwest::{Method, StatusCode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOperation {
+    Create,
+    Delete,
+    Pull,
+    Push,
+    /// An operation unknown to this crate, never used in queries.
+    #[serde(other)]
+    Other,
+}
+
+/// enum to the value used by Harbor
+///
+/// ```rust
+/// use harbor_rs::harbor::audit::AuditOperation;
+///
+/// assert_eq!(AuditOperation::Delete.to_string(), "delete");
+/// ```
+impl fmt::Display for AuditOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", format!("{:?}", self).to_lowercase())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLog {
+    pub id: i64,
+    /// The username of the operator.
+    pub username: String,
+    /// The resource of the operation, e.g. `library/nginx:1.0`.
+    pub resource: String,
+    /// The resource type of the operation, e.g. `artifact`.
+    pub resource_type: String,
+    pub operation: AuditOperation,
+    pub op_time: String,
+}
+
+/// The filters of audit logs, the unset filters are ignored.
+///
+/// ```rust
+/// use harbor_rs::harbor::audit::{AuditLogQuery, AuditOperation};
+///
+/// let query = AuditLogQuery {
+///     operation: Some(AuditOperation::Delete),
+///     resource: Some(String::from("library/nginx")),
+///     ..Default::default()
+/// };
+/// assert_eq!(query.to_q(), "operation=delete,resource=~library/nginx");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogQuery {
+    /// Exact match of the operator.
+    pub username: Option<String>,
+    pub operation: Option<AuditOperation>,
+    /// Fuzzy match of the resource.
+    pub resource: Option<String>,
+    pub resource_type: Option<String>,
+    /// Begin of the operation time range, inclusive.
+    pub begin: Option<DateTime<Utc>>,
+    /// End of the operation time range, inclusive.
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl AuditLogQuery {
+    /// Build the `q` query parameter of Harbor, the values are percent-encoded
+    /// as `,` and `=` separate the conditions.
+    pub fn to_q(&self) -> String {
+        let mut q = Vec::new();
+        if let Some(username) = &self.username {
+            q.push(format!("username={}", escape_q_value(username)));
+        }
+        if let Some(operation) = &self.operation {
+            q.push(format!("operation={}", operation));
+        }
+        if let Some(resource) = &self.resource {
+            q.push(format!("resource=~{}", escape_q_value(resource)));
+        }
+        if let Some(resource_type) = &self.resource_type {
+            q.push(format!("resource_type={}", escape_q_value(resource_type)));
+        }
+        if self.begin.is_some() || self.end.is_some() {
+            let format_time = |t: &Option<DateTime<Utc>>| {
+                t.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default()
+            };
+            q.push(format!("op_time=[{}~{}]", format_time(&self.begin), format_time(&self.end)));
+        }
+        q.join(",")
+    }
+}
+
+/// Percent-encode the characters with a meaning in the `q` syntax.
+fn escape_q_value(value: &str) -> String {
+    value.replace('%', "%25").replace(',', "%2C").replace('=', "%3D")
+}
+
+impl Client {
+    /// List audit logs of the system, or of the project if `project_name` is set.
+    pub async fn list_audit_logs(&self, project_name: Option<&str>, query: &AuditLogQuery, page: Option<u32>, page_size: Option<u32>) -> Result<Vec<AuditLog>> {
+        let path = match project_name {
+            Some(project_name) => format!("/projects/{}/logs", project_name),
+            None => String::from("/audit-logs"),
+        };
+        let mut params = Vec::new();
+        let q = query.to_q();
+        if !q.is_empty() {
+            params.push(("q", q));
+        }
+        if let Some(page) = page {
+            params.push(("page", page.to_string()));
+        }
+        if let Some(page_size) = page_size {
+            params.push(("page_size", page_size.to_string()));
+        }
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<AuditLog>>().await?)
+        } else {
+            Err(anyhow!("failed to list audit logs: {}", resp.text().await?))
+        }
+    }
+
+    /// Stream all audit logs matching the query, page by page, `page_size` must not be zero.
+    ///
+    /// ```rust,no_run
+    /// use futures::TryStreamExt;
+    /// use harbor_rs::harbor::audit::AuditLogQuery;
+    ///
+    /// # async fn run(client: &harbor_rs::Client) -> anyhow::Result<()> {
+    /// let logs = client.audit_logs_stream(None, AuditLogQuery::default(), 100);
+    /// futures::pin_mut!(logs);
+    /// while let Some(log) = logs.try_next().await? {
+    ///     println!("{} {} {}", log.username, log.operation, log.resource);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn audit_logs_stream<'a>(&'a self, project_name: Option<&'a str>, query: AuditLogQuery, page_size: u32) -> impl Stream<Item = Result<AuditLog>> + 'a {
+        stream::try_unfold((query, Some(1)), move |(query, page)| async move {
+            let page = match page {
+                Some(page) => page,
+                None => return Ok(None),
+            };
+            if page_size == 0 {
+                return Err(anyhow!("page size of audit logs must not be zero"));
+            }
+            let logs = self.list_audit_logs(project_name, &query, Some(page), Some(page_size)).await?;
+            if logs.is_empty() {
+                return Ok(None);
+            }
+            let next = if (logs.len() as u32) < page_size { None } else { Some(page + 1) };
+            Ok(Some((stream::iter(logs.into_iter().map(Ok)), (query, next))))
+        }).try_flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuditLog, AuditLogQuery, AuditOperation};
+    use crate::harbor::testing;
+    use crate::Client;
+    use chrono::{TimeZone, Utc};
+    use futures::TryStreamExt;
+
+    #[test]
+    fn audit_log_query_to_q() {
+        assert_eq!(AuditLogQuery::default().to_q(), "");
+        let mut query = AuditLogQuery {
+            username: Some(String::from("admin")),
+            operation: Some(AuditOperation::Push),
+            resource_type: Some(String::from("artifact")),
+            begin: Some(Utc.with_ymd_and_hms(2021, 12, 1, 0, 0, 0).unwrap()),
+            end: Some(Utc.with_ymd_and_hms(2021, 12, 31, 23, 59, 59).unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(query.to_q(), "username=admin,operation=push,resource_type=artifact,op_time=[2021-12-01 00:00:00~2021-12-31 23:59:59]");
+        query.end = None;
+        assert!(query.to_q().ends_with("op_time=[2021-12-01 00:00:00~]"));
+        query.begin = None;
+        query.end = Some(Utc.with_ymd_and_hms(2021, 12, 31, 23, 59, 59).unwrap());
+        assert!(query.to_q().ends_with("op_time=[~2021-12-31 23:59:59]"));
+    }
+
+    #[test]
+    fn audit_log_query_escape() {
+        let query = AuditLogQuery {
+            username: Some(String::from("a,b=c")),
+            resource: Some(String::from("100%")),
+            ..Default::default()
+        };
+        assert_eq!(query.to_q(), "username=a%2Cb%3Dc,resource=~100%25");
+    }
+
+    #[test]
+    fn parse_unknown_operation() {
+        let s = r#"{"id":1,"username":"admin","resource":"library/nginx","resource_type":"artifact","operation":"scan","op_time":"2021-12-01T00:00:00Z"}"#;
+        let log: AuditLog = serde_json::from_str(s).unwrap();
+        assert_eq!(log.operation, AuditOperation::Other);
+    }
+
+    #[tokio::test]
+    async fn audit_logs_stream_pages() {
+        // 3 logs on the first page, and the second page is empty
+        let base_url = testing::serve(|path| {
+            let log = r#"{"id":1,"username":"admin","resource":"library/nginx","resource_type":"artifact","operation":"push","op_time":"2021-12-01T00:00:00Z"}"#;
+            if path.contains("page=1&") {
+                (200, format!("[{},{},{}]", log, log, log))
+            } else {
+                (200, String::from("[]"))
+            }
+        }).await;
+        let client = Client::new(base_url, String::new(), String::new()).unwrap();
+        let logs: Vec<AuditLog> = client.audit_logs_stream(None, AuditLogQuery::default(), 3).try_collect().await.unwrap();
+        assert_eq!(logs.len(), 3);
+
+        let err = client.audit_logs_stream(None, AuditLogQuery::default(), 0).try_collect::<Vec<AuditLog>>().await.unwrap_err();
+        assert!(err.to_string().contains("must not be zero"), "{}", err);
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::path::Path;
+use super::client::Client;
+use super::common::Label;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use anyhow::{anyhow, Result};
+use reqwest::multipart::{Form, Part};
+use reqwest::{Method, StatusCode};
+
+/// The chart in the chart repository of a project.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChartInfo {
+    pub name: String,
+    pub total_versions: i64,
+    pub latest_version: String,
+    pub created: String,
+    pub updated: Option<String>,
+    pub icon: Option<String>,
+    pub home: Option<String>,
+    #[serde(default)]
+    pub deprecated: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChartMaintainer {
+    pub name: String,
+    pub email: Option<String>,
+    pub url: Option<String>,
+}
+
+/// The dependency of a chart, from the Chart.yaml or requirements.yaml.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChartDependency {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub repository: String,
+}
+
+/// The metadata of a chart in the Chart.yaml.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChartMetadata {
+    pub name: String,
+    pub version: String,
+    /// Left out by the charts packaged with Helm v2.
+    #[serde(rename = "apiVersion", default)]
+    pub api_version: String,
+    #[serde(rename = "appVersion")]
+    pub app_version: Option<String>,
+    #[serde(rename = "kubeVersion")]
+    pub kube_version: Option<String>,
+    pub description: Option<String>,
+    pub home: Option<String>,
+    pub icon: Option<String>,
+    #[serde(rename = "type")]
+    pub chart_type: Option<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub sources: Vec<String>,
+    #[serde(default)]
+    pub maintainers: Vec<ChartMaintainer>,
+    #[serde(default)]
+    pub dependencies: Vec<ChartDependency>,
+    pub engine: Option<String>,
+    #[serde(default)]
+    pub deprecated: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChartVersion {
+    #[serde(flatten)]
+    pub metadata: ChartMetadata,
+    #[serde(default)]
+    pub urls: Vec<String>,
+    pub created: String,
+    pub digest: String,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChartVersionDetails {
+    pub metadata: ChartVersion,
+    #[serde(default)]
+    pub dependencies: Vec<ChartDependency>,
+    /// The default values of the chart.
+    #[serde(default)]
+    pub values: HashMap<String, Value>,
+    /// The files of the chart, e.g. README.md.
+    #[serde(default)]
+    pub files: HashMap<String, String>,
+    pub security: Option<Value>,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+}
+
+impl Client {
+    /// Get all the charts under the repository, the repository is the project name.
+    pub async fn list_charts(&self, repo: &str) -> Result<Vec<ChartInfo>> {
+        let path = format!("/chartrepo/{}/charts", repo);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<ChartInfo>>().await?)
+        } else {
+            Err(anyhow!("failed to list charts: {}", resp.text().await?))
+        }
+    }
+
+    /// Get all the versions of the chart.
+    pub async fn list_chart_versions(&self, repo: &str, name: &str) -> Result<Vec<ChartVersion>> {
+        let path = format!("/chartrepo/{}/charts/{}", repo, name);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<ChartVersion>>().await?)
+        } else {
+            Err(anyhow!("failed to list chart versions: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the details of the chart version.
+    pub async fn get_chart_version(&self, repo: &str, name: &str, version: &str) -> Result<ChartVersionDetails> {
+        let path = format!("/chartrepo/{}/charts/{}/{}", repo, name, version);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<ChartVersionDetails>().await?)
+        } else {
+            Err(anyhow!("failed to get chart version: {}", resp.text().await?))
+        }
+    }
+
+    /// Upload a chart `.tgz` file to the repository, with the optional provenance file.
+    pub async fn upload_chart(&self, repo: &str, chart: &Path, prov: Option<&Path>) -> Result<()> {
+        let path = format!("/chartrepo/{}/charts", repo);
+        let mut form = Form::new().part("chart", file_part(chart).await?);
+        if let Some(prov) = prov {
+            form = form.part("prov", file_part(prov).await?);
+        }
+        let resp = self.build_request(Method::POST, path).multipart(form).send().await?;
+        if resp.status().eq(&StatusCode::CREATED) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to upload chart: {}", resp.text().await?))
+        }
+    }
+
+    /// Delete the chart version.
+    pub async fn delete_chart_version(&self, repo: &str, name: &str, version: &str) -> Result<()> {
+        let path = format!("/chartrepo/{}/charts/{}/{}", repo, name, version);
+        let resp = self.build_request(Method::DELETE, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to delete chart version: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the labels of the chart version.
+    pub async fn list_chart_version_labels(&self, repo: &str, name: &str, version: &str) -> Result<Vec<Label>> {
+        let path = format!("/chartrepo/{}/charts/{}/{}/labels", repo, name, version);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<Label>>().await?)
+        } else {
+            Err(anyhow!("failed to list chart version labels: {}", resp.text().await?))
+        }
+    }
+
+    /// Mark the label to the chart version.
+    pub async fn add_chart_version_label(&self, repo: &str, name: &str, version: &str, label_id: u64) -> Result<()> {
+        let path = format!("/chartrepo/{}/charts/{}/{}/labels", repo, name, version);
+        let payload = json!({ "id": label_id });
+        let resp = self.build_request(Method::POST, path).json(&payload).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to add chart version label: {}", resp.text().await?))
+        }
+    }
+
+    /// Remove the label from the chart version.
+    pub async fn remove_chart_version_label(&self, repo: &str, name: &str, version: &str, label_id: u64) -> Result<()> {
+        let path = format!("/chartrepo/{}/charts/{}/{}/labels/{}", repo, name, version, label_id);
+        let resp = self.build_request(Method::DELETE, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to remove chart version label: {}", resp.text().await?))
+        }
+    }
+}
+
+async fn file_part(path: &Path) -> Result<Part> {
+    let file_name = path.file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| anyhow!("invalid file path: {}", path.display()))?
+        .to_string();
+    let content = tokio::fs::read(path).await?;
+    Ok(Part::bytes(content).file_name(file_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChartVersion;
+
+    #[test]
+    fn parse_chart_version() {
+        let s = r#"{
+            "name": "nginx",
+            "version": "1.0.0",
+            "apiVersion": "v2",
+            "appVersion": "1.21",
+            "dependencies": [{"name": "common", "version": "1.x.x", "repository": "https://charts.bitnami.com/bitnami"}],
+            "urls": ["charts/nginx-1.0.0.tgz"],
+            "created": "2021-12-02T04:35:12.923959Z",
+            "digest": "sha256:1234",
+            "labels": []
+        }"#;
+        let version: ChartVersion = serde_json::from_str(s).unwrap();
+        assert_eq!(version.metadata.app_version.as_deref(), Some("1.21"));
+        assert_eq!(version.metadata.dependencies[0].name, "common");
+    }
+}
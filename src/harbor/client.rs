@@ -31,10 +31,14 @@ impl Client {
         let base_url: &'static str = option_env!("HARBOR_BASE_URL").ok_or(anyhow!("HARBOR_BASE_URL not set"))?;
         let username: &'static str = option_env!("HARBOR_USERNAME").ok_or(anyhow!("HARBOR_USERNAME not set"))?;
         let password: &'static str = option_env!("HARBOR_PASSWORD").ok_or(anyhow!("HARBOR_PASSWORD not set"))?;
-        Ok(self::Client::new(
+        self::Client::new(
             base_url.to_string(),
             username.to_string(),
-            password.to_string())?)
+            password.to_string())
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
     }
 
     pub fn build_request<U: IntoUrl>(&self, method: Method, path: U) -> reqwest::RequestBuilder {
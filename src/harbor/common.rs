@@ -20,13 +20,73 @@ pub struct Label {
     pub name: String
 }
 
+/// The decoration of a selector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SelectorDecoration {
+    Matches,
+    Excludes,
+    RepoMatches,
+    RepoExcludes,
+}
+
+/// The selector used by tag immutability and tag retention rules.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Selector {
+    /// The selector kind, only `doublestar` is supported.
+    pub kind: String,
+    pub decoration: SelectorDecoration,
+    /// The doublestar pattern, e.g. `**` or `release-*`.
+    pub pattern: String,
+    /// The JSON encoded extra options, e.g. `{"untagged":true}` of the retention tag selector.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extras: Option<String>,
+}
+
+impl Selector {
+    pub fn new(decoration: SelectorDecoration, pattern: &str) -> Self {
+        Selector {
+            kind: String::from("doublestar"),
+            decoration,
+            pattern: pattern.to_string(),
+            extras: None,
+        }
+    }
+
+    /// Select tags matching the pattern.
+    pub fn tag_matches(pattern: &str) -> Self {
+        Selector::new(SelectorDecoration::Matches, pattern)
+    }
+
+    /// Select tags not matching the pattern.
+    pub fn tag_excludes(pattern: &str) -> Self {
+        Selector::new(SelectorDecoration::Excludes, pattern)
+    }
+
+    /// Select repositories matching the pattern.
+    pub fn repo_matches(pattern: &str) -> Self {
+        Selector::new(SelectorDecoration::RepoMatches, pattern)
+    }
+
+    /// Select repositories not matching the pattern.
+    pub fn repo_excludes(pattern: &str) -> Self {
+        Selector::new(SelectorDecoration::RepoExcludes, pattern)
+    }
+}
+
+/// The repository scope of a tag immutability or tag retention rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScopeSelectors {
+    pub repository: Vec<Selector>,
+}
+
 pub fn parse_time(time: &str) -> Result<DateTime<Utc>> {
     Ok(time.parse::<DateTime<Utc>>()?)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::harbor::common::parse_time;
+    use crate::harbor::common::{parse_time, Selector};
 
     #[test]
     fn test_parse_time() {
@@ -43,4 +103,16 @@ mod tests {
             assert_eq!(t.format("%Y-%m-%d %H:%M:%S").to_string(), d.c);
         }
     }
+
+    #[test]
+    fn test_selector_serde() {
+        let selector = Selector::repo_excludes("library/**");
+        let s = serde_json::to_string(&selector).unwrap();
+        assert_eq!(s, r#"{"kind":"doublestar","decoration":"repoExcludes","pattern":"library/**"}"#);
+        assert_eq!(serde_json::from_str::<Selector>(&s).unwrap(), selector);
+        let s = r#"{"kind":"doublestar","decoration":"matches","pattern":"**","extras":"{\"untagged\":true}"}"#;
+        let selector = serde_json::from_str::<Selector>(s).unwrap();
+        assert_eq!(selector.extras.as_deref(), Some(r#"{"untagged":true}"#));
+        assert_eq!(serde_json::to_string(&selector).unwrap(), s);
+    }
 }
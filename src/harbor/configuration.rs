@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::fmt;
+use super::client::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use anyhow::{anyhow, Result};
+use reqwest::{Method, StatusCode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMode {
+    DbAuth,
+    LdapAuth,
+    UaaAuth,
+    OidcAuth,
+    HttpAuth,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectCreationRestriction {
+    Everyone,
+    AdminOnly,
+}
+
+/// The configuration item returned by Harbor.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ConfigItem<T> {
+    pub value: T,
+    pub editable: bool,
+}
+
+/// The system configurations, the secrets such as passwords are never returned by Harbor.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Configurations {
+    pub auth_mode: Option<ConfigItem<AuthMode>>,
+    pub project_creation_restriction: Option<ConfigItem<ProjectCreationRestriction>>,
+    pub read_only: Option<ConfigItem<bool>>,
+    pub self_registration: Option<ConfigItem<bool>>,
+    /// The expiration time of the token for internal registry, in minutes.
+    pub token_expiration: Option<ConfigItem<i64>>,
+    /// The robot account token duration in days.
+    pub robot_token_duration: Option<ConfigItem<i64>>,
+    pub notification_enable: Option<ConfigItem<bool>>,
+    pub email_host: Option<ConfigItem<String>>,
+    pub email_port: Option<ConfigItem<i64>>,
+    pub email_from: Option<ConfigItem<String>>,
+    pub email_username: Option<ConfigItem<String>>,
+    pub email_ssl: Option<ConfigItem<bool>>,
+    pub email_insecure: Option<ConfigItem<bool>>,
+    pub ldap_url: Option<ConfigItem<String>>,
+    pub ldap_base_dn: Option<ConfigItem<String>>,
+    pub ldap_filter: Option<ConfigItem<String>>,
+    pub ldap_scope: Option<ConfigItem<i64>>,
+    pub ldap_uid: Option<ConfigItem<String>>,
+    pub ldap_search_dn: Option<ConfigItem<String>>,
+    pub ldap_timeout: Option<ConfigItem<i64>>,
+    pub ldap_verify_cert: Option<ConfigItem<bool>>,
+    pub oidc_name: Option<ConfigItem<String>>,
+    pub oidc_endpoint: Option<ConfigItem<String>>,
+    pub oidc_client_id: Option<ConfigItem<String>>,
+    /// The configurations not modeled above, e.g. the ones added by newer Harbor versions.
+    #[serde(flatten)]
+    pub others: HashMap<String, ConfigItem<Value>>,
+}
+
+/// The configurations to update, the unset ones are left unchanged.
+///
+/// ```rust
+/// use harbor_rs::harbor::configuration::{ConfigurationsUpdate, ProjectCreationRestriction};
+///
+/// let update = ConfigurationsUpdate {
+///     project_creation_restriction: Some(ProjectCreationRestriction::AdminOnly),
+///     read_only: Some(true),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct ConfigurationsUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_mode: Option<AuthMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_creation_restriction: Option<ProjectCreationRestriction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub self_registration: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_expiration: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub robot_token_duration: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notification_enable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_host: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_port: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_username: Option<String>,
+    /// Write-only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_ssl: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_insecure: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_base_dn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_filter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_scope: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_uid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_search_dn: Option<String>,
+    /// Write-only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_search_password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_timeout: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_verify_cert: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oidc_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oidc_endpoint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oidc_client_id: Option<String>,
+    /// Write-only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oidc_client_secret: Option<String>,
+    /// The configurations not modeled above.
+    #[serde(flatten)]
+    pub others: HashMap<String, Value>,
+}
+
+impl fmt::Debug for ConfigurationsUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ConfigurationsUpdate")
+            .field("auth_mode", &self.auth_mode)
+            .field("project_creation_restriction", &self.project_creation_restriction)
+            .field("read_only", &self.read_only)
+            .field("self_registration", &self.self_registration)
+            .field("token_expiration", &self.token_expiration)
+            .field("robot_token_duration", &self.robot_token_duration)
+            .field("notification_enable", &self.notification_enable)
+            .field("email_host", &self.email_host)
+            .field("email_port", &self.email_port)
+            .field("email_from", &self.email_from)
+            .field("email_username", &self.email_username)
+            .field("email_password", &self.email_password.as_ref().map(|_| "******"))
+            .field("email_ssl", &self.email_ssl)
+            .field("email_insecure", &self.email_insecure)
+            .field("ldap_url", &self.ldap_url)
+            .field("ldap_base_dn", &self.ldap_base_dn)
+            .field("ldap_filter", &self.ldap_filter)
+            .field("ldap_scope", &self.ldap_scope)
+            .field("ldap_uid", &self.ldap_uid)
+            .field("ldap_search_dn", &self.ldap_search_dn)
+            .field("ldap_search_password", &self.ldap_search_password.as_ref().map(|_| "******"))
+            .field("ldap_timeout", &self.ldap_timeout)
+            .field("ldap_verify_cert", &self.ldap_verify_cert)
+            .field("oidc_name", &self.oidc_name)
+            .field("oidc_endpoint", &self.oidc_endpoint)
+            .field("oidc_client_id", &self.oidc_client_id)
+            .field("oidc_client_secret", &self.oidc_client_secret.as_ref().map(|_| "******"))
+            // the unknown keys may be secrets as well
+            .field("others", &self.others.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Client {
+    /// Get system configurations.
+    pub async fn get_configurations(&self) -> Result<Configurations> {
+        let path = "/configurations";
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Configurations>().await?)
+        } else {
+            Err(anyhow!("failed to get configurations: {}", resp.text().await?))
+        }
+    }
+
+    /// Modify system configurations.
+    pub async fn update_configurations(&self, configurations: &ConfigurationsUpdate) -> Result<()> {
+        let path = "/configurations";
+        let resp = self.build_request(Method::PUT, path).json(configurations).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to update configurations: {}", resp.text().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::{AuthMode, Configurations, ConfigurationsUpdate};
+
+    #[test]
+    fn configurations_serde() {
+        let s = r#"{
+            "auth_mode": {"value": "ldap_auth", "editable": false},
+            "read_only": {"value": false, "editable": true},
+            "audit_log_forward_endpoint": {"value": "", "editable": true}
+        }"#;
+        let configurations: Configurations = serde_json::from_str(s).unwrap();
+        assert_eq!(configurations.auth_mode.unwrap().value, AuthMode::LdapAuth);
+        assert!(configurations.others.contains_key("audit_log_forward_endpoint"));
+
+        let mut update = ConfigurationsUpdate {
+            read_only: Some(true),
+            ldap_search_password: Some(String::from("Passw0rd")),
+            ..Default::default()
+        };
+        update.others.insert(String::from("audit_log_forward_endpoint"), json!("syslog:514"));
+        let value = serde_json::to_value(&update).unwrap();
+        assert_eq!(value, json!({
+            "read_only": true,
+            "ldap_search_password": "Passw0rd",
+            "audit_log_forward_endpoint": "syslog:514"
+        }));
+        let debug = format!("{:?}", update);
+        assert!(debug.contains("audit_log_forward_endpoint"));
+        assert!(!debug.contains("Passw0rd") && !debug.contains("syslog"));
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt;
+use super::client::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use anyhow::{anyhow, Result};
+use reqwest::{Method, StatusCode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RegistryType {
+    #[serde(rename = "harbor")]
+    Harbor,
+    #[serde(rename = "docker-hub")]
+    DockerHub,
+    #[serde(rename = "docker-registry")]
+    DockerRegistry,
+    #[serde(rename = "huawei-SWR")]
+    HuaweiSwr,
+    #[serde(rename = "google-gcr")]
+    GoogleGcr,
+    #[serde(rename = "aws-ecr")]
+    AwsEcr,
+    #[serde(rename = "azure-acr")]
+    AzureAcr,
+    #[serde(rename = "ali-acr")]
+    AliAcr,
+    #[serde(rename = "jfrog-artifactory")]
+    JfrogArtifactory,
+    /// Quay of Harbor v1.10 and v2.0.
+    #[serde(rename = "quay-io")]
+    QuayIo,
+    /// Quay since Harbor v2.1, which renamed `quay-io`.
+    #[serde(rename = "quay")]
+    Quay,
+    #[serde(rename = "helm-hub")]
+    HelmHub,
+    #[serde(rename = "gitlab")]
+    Gitlab,
+    #[serde(rename = "dtr")]
+    Dtr,
+    #[serde(rename = "tencent-tcr")]
+    TencentTcr,
+    #[serde(rename = "github-ghcr")]
+    GithubGhcr,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CredentialType {
+    Basic,
+    OAuth,
+    Secret,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct RegistryCredential {
+    #[serde(rename = "type")]
+    pub credential_type: CredentialType,
+    #[serde(default)]
+    pub access_key: String,
+    #[serde(default)]
+    pub access_secret: String,
+}
+
+impl RegistryCredential {
+    /// Whether the secret is the mask returned by Harbor instead of the stored secret.
+    pub fn is_secret_masked(&self) -> bool {
+        !self.access_secret.is_empty() && self.access_secret.chars().all(|c| c == '*')
+    }
+}
+
+impl fmt::Debug for RegistryCredential {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RegistryCredential")
+            .field("credential_type", &self.credential_type)
+            .field("access_key", &self.access_key)
+            .field("access_secret", &"******")
+            .finish()
+    }
+}
+
+/// The registry endpoint used by replication and proxy cache.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Registry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub registry_type: RegistryType,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential: Option<RegistryCredential>,
+    /// Whether or not to verify the certificate of the registry.
+    #[serde(default)]
+    pub insecure: bool,
+    #[serde(default)]
+    pub description: String,
+    /// The health status of the registry, e.g. `healthy`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creation_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_time: Option<String>,
+}
+
+impl Registry {
+    pub fn new(name: String, registry_type: RegistryType, url: String, credential: Option<RegistryCredential>) -> Self {
+        Registry {
+            id: None,
+            name,
+            registry_type,
+            url,
+            credential,
+            insecure: false,
+            description: String::new(),
+            status: None,
+            creation_time: None,
+            update_time: None,
+        }
+    }
+
+    /// The flat payload used by the update and ping requests, a masked secret is left out
+    /// so that Harbor keeps the stored one.
+    fn flat_payload(&self) -> Value {
+        let mut payload = json!({
+            "name": self.name,
+            "type": self.registry_type,
+            "url": self.url,
+            "insecure": self.insecure,
+            "description": self.description,
+        });
+        if let Some(id) = self.id {
+            payload["id"] = json!(id);
+        }
+        if let Some(credential) = &self.credential {
+            payload["credential_type"] = json!(credential.credential_type);
+            payload["access_key"] = json!(credential.access_key);
+            if !credential.is_secret_masked() {
+                payload["access_secret"] = json!(credential.access_secret);
+            }
+        }
+        payload
+    }
+}
+
+impl Client {
+    /// List registries.
+    pub async fn list_registries(&self, name: Option<String>) -> Result<Vec<Registry>> {
+        let path = "/registries";
+        let mut params = Vec::new();
+        if let Some(name) = name {
+            params.push(("name", name));
+        }
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<Registry>>().await?)
+        } else {
+            Err(anyhow!("failed to list registries: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the specific registry.
+    pub async fn get_registry(&self, id: i64) -> Result<Registry> {
+        let path = format!("/registries/{}", id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Registry>().await?)
+        } else {
+            Err(anyhow!("failed to get registry: {}", resp.text().await?))
+        }
+    }
+
+    /// Create a registry.
+    pub async fn create_registry(&self, registry: &Registry) -> Result<()> {
+        let path = "/registries";
+        let resp = self.build_request(Method::POST, path).json(registry).send().await?;
+        if resp.status().eq(&StatusCode::CREATED) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to create registry: {}", resp.text().await?))
+        }
+    }
+
+    /// Update the registry.
+    pub async fn update_registry(&self, id: i64, registry: &Registry) -> Result<()> {
+        let path = format!("/registries/{}", id);
+        let resp = self.build_request(Method::PUT, path).json(&registry.flat_payload()).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to update registry: {}", resp.text().await?))
+        }
+    }
+
+    /// Delete the registry.
+    pub async fn delete_registry(&self, id: i64) -> Result<()> {
+        let path = format!("/registries/{}", id);
+        let resp = self.build_request(Method::DELETE, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to delete registry: {}", resp.text().await?))
+        }
+    }
+
+    /// Check the connectivity of the registry before saving it.
+    pub async fn ping_registry(&self, registry: &Registry) -> Result<()> {
+        let path = "/registries/ping";
+        let resp = self.build_request(Method::POST, path).json(&registry.flat_payload()).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to ping registry: {}", resp.text().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CredentialType, Registry, RegistryCredential, RegistryType};
+
+    #[test]
+    fn registry_serde() {
+        let credential = RegistryCredential {
+            credential_type: CredentialType::Basic,
+            access_key: String::from("admin"),
+            access_secret: String::from("Harbor12345"),
+        };
+        assert!(!format!("{:?}", credential).contains("Harbor12345"));
+        let registry = Registry::new(String::from("hub"), RegistryType::DockerHub, String::from("https://hub.docker.com"), Some(credential));
+        let value = serde_json::to_value(&registry).unwrap();
+        assert_eq!(value["type"], "docker-hub");
+        assert_eq!(value["credential"]["type"], "basic");
+        let payload = registry.flat_payload();
+        assert_eq!(payload["credential_type"], "basic");
+        assert_eq!(payload["access_secret"], "Harbor12345");
+        let registry: Registry = serde_json::from_str(r#"{"id":1,"name":"q","type":"quay-io","url":"https://quay.io"}"#).unwrap();
+        assert_eq!(registry.registry_type, RegistryType::QuayIo);
+        assert_eq!(serde_json::to_value(RegistryType::QuayIo).unwrap(), "quay-io");
+        assert_eq!(serde_json::to_value(RegistryType::Quay).unwrap(), "quay");
+        let s = r#"{"id":2,"name":"h","type":"harbor","url":"https://h.example.com","credential":{"type":"basic","access_key":"admin","access_secret":"*****"}}"#;
+        let registry: Registry = serde_json::from_str(s).unwrap();
+        let payload = registry.flat_payload();
+        assert_eq!(payload["access_key"], "admin");
+        assert!(payload.get("access_secret").is_none());
+    }
+}
@@ -1,24 +1,47 @@
 use std::fmt;
 use super::client::Client;
-use serde::{Deserialize, Serialize};
+use super::job::{job_id_from_location, JobHandle, JobKind};
+use serde::{Deserialize, Deserializer, Serialize};
 use anyhow::{anyhow, Result};
 use reqwest::Method;
-use serde_json::json;
 
-#[derive(Debug)]
+/// The type of a schedule, parsed case-insensitively as Harbor v2 reports e.g. `MANUAL`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum ScheduleType {
     Hourly,
     Daily,
     Weekly,
     Custom,
     Manual,
-    None
+    None,
+    /// A cron based schedule, as reported by Harbor v2.
+    Schedule,
+    /// A type unknown to this crate.
+    Unknown,
+}
+
+impl<'de> Deserialize<'de> for ScheduleType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(match s.to_lowercase().as_str() {
+            "hourly" => ScheduleType::Hourly,
+            "daily" => ScheduleType::Daily,
+            "weekly" => ScheduleType::Weekly,
+            "custom" => ScheduleType::Custom,
+            "manual" => ScheduleType::Manual,
+            "none" | "" => ScheduleType::None,
+            "schedule" => ScheduleType::Schedule,
+            _ => ScheduleType::Unknown,
+        })
+    }
 }
 
 /// enum to String
 ///
 /// ```rust
-/// ScheduleType::Hourly.to_string() // "Hourly"
+/// use harbor_rs::harbor::gc::ScheduleType;
+///
+/// assert_eq!(ScheduleType::Hourly.to_string(), "Hourly");
 /// ```
 impl fmt::Display for ScheduleType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -26,14 +49,128 @@ impl fmt::Display for ScheduleType {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Schedule {
     #[serde(rename = "type")]
-    pub schedule_type: String,
+    pub schedule_type: ScheduleType,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cron: Option<String>
 }
 
+impl Schedule {
+    pub fn new(schedule_type: ScheduleType, cron: Option<String>) -> Self {
+        Schedule { schedule_type, cron }
+    }
+
+    /// Run the job once immediately.
+    pub fn manual() -> Self {
+        Schedule::new(ScheduleType::Manual, None)
+    }
+
+    /// Run the job with a custom cron.
+    ///
+    /// ```rust
+    /// use harbor_rs::harbor::gc::Schedule;
+    ///
+    /// assert!(Schedule::custom("0 0 2 * * *").is_ok());
+    /// assert!(Schedule::custom("0 2 * * *").is_err());
+    /// ```
+    pub fn custom(cron: &str) -> Result<Self> {
+        let schedule = Schedule::new(ScheduleType::Custom, Some(cron.to_string()));
+        schedule.validate()?;
+        Ok(schedule)
+    }
+
+    /// Validate the schedule, the cron is required by the `Custom` and `Schedule` types.
+    pub fn validate(&self) -> Result<()> {
+        match &self.cron {
+            Some(cron) if !cron.is_empty() => validate_cron(cron),
+            _ if matches!(self.schedule_type, ScheduleType::Custom | ScheduleType::Schedule) => {
+                Err(anyhow!("cron is required by the {} schedule type", self.schedule_type))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Validate the cron used by Harbor, which has 6 fields:
+/// second, minute, hour, day of month, month and day of week.
+pub fn validate_cron(cron: &str) -> Result<()> {
+    let fields: Vec<&str> = cron.split_whitespace().collect();
+    if fields.len() != 6 {
+        return Err(anyhow!("invalid cron {}: expected 6 fields, got {}", cron, fields.len()));
+    }
+    let names = ["second", "minute", "hour", "day of month", "month", "day of week"];
+    let ranges = [(0, 59), (0, 59), (0, 23), (1, 31), (1, 12), (0, 6)];
+    let aliases: [&[&str]; 6] = [&[], &[], &[], &[], &MONTH_NAMES, &WEEKDAY_NAMES];
+    for (((field, name), (min, max)), aliases) in fields.iter().zip(names).zip(ranges).zip(aliases) {
+        if !valid_cron_field(field, min, max, aliases) {
+            return Err(anyhow!("invalid cron {}: invalid {} field {}", cron, name, field));
+        }
+    }
+    Ok(())
+}
+
+const MONTH_NAMES: [&str; 12] = ["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+const WEEKDAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Validate the cron field, `aliases` are the names of the values from `min`, e.g. JAN for the month.
+fn valid_cron_field(field: &str, min: u32, max: u32, aliases: &[&str]) -> bool {
+    let value = |v: &str| -> Option<u32> {
+        match v.parse::<u32>() {
+            Ok(n) if n >= min && n <= max => Some(n),
+            Ok(_) => None,
+            Err(_) => aliases.iter()
+                .position(|a| a.eq_ignore_ascii_case(v))
+                .map(|i| min + i as u32),
+        }
+    };
+    field.split(',').all(|part| {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, Some(step)),
+            None => (part, None),
+        };
+        if let Some(step) = step {
+            if !matches!(step.parse::<u32>(), Ok(n) if n > 0) {
+                return false;
+            }
+        }
+        match range {
+            "*" | "?" => true,
+            _ => match range.split_once('-') {
+                Some((start, end)) => matches!((value(start), value(end)), (Some(start), Some(end)) if start <= end),
+                None => value(range).is_some(),
+            },
+        }
+    })
+}
+
+/// The parameters of gc, available since Harbor v2.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GCParameters {
+    /// Delete the untagged artifacts.
+    pub delete_untagged: bool,
+    /// Only show what would be deleted.
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GCSchedule {
+    pub schedule: Option<Schedule>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<GCParameters>,
+}
+
+impl GCSchedule {
+    fn new(schedule: &Schedule, parameters: Option<&GCParameters>) -> Self {
+        GCSchedule {
+            schedule: Some(schedule.clone()),
+            parameters: parameters.cloned(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct GCResult {
     pub job_status: String,
@@ -48,9 +185,10 @@ pub struct GCResult {
 
 impl Client {
     /// Create a gc schedule.
-    pub async fn create_schedule(&self, schedule: &Schedule) -> Result<()> {
+    pub async fn create_schedule(&self, schedule: &Schedule, parameters: Option<&GCParameters>) -> Result<()> {
+        schedule.validate()?;
         let path = "/system/gc/schedule";
-        let payload = json!({"schedule": schedule});
+        let payload = GCSchedule::new(schedule, parameters);
         let resp = self.build_request(Method::POST, path)
             .json(&payload)
             .send()
@@ -62,6 +200,58 @@ impl Client {
         }
     }
 
+    /// Run gc immediately, and return the handle of the gc job.
+    pub async fn trigger_gc(&self, parameters: Option<&GCParameters>) -> Result<JobHandle<'_>> {
+        let last_id = self.list_gc_results().await?.iter().map(|r| r.id).max().unwrap_or(0);
+        let path = "/system/gc/schedule";
+        let payload = GCSchedule::new(&Schedule::manual(), parameters);
+        let resp = self.build_request(Method::POST, path)
+            .json(&payload)
+            .send()
+            .await?;
+        if !resp.status().eq(&reqwest::StatusCode::CREATED) {
+            return Err(anyhow!("failed to trigger gc: {}", resp.text().await?));
+        }
+        let id = match job_id_from_location(&resp) {
+            Some(id) => id,
+            // the first gc job created after the trigger
+            None => self.list_gc_results().await?
+                .iter()
+                .map(|r| r.id)
+                .filter(|id| *id > last_id)
+                .min()
+                .ok_or_else(|| anyhow!("failed to find the triggered gc job"))? as i64,
+        };
+        Ok(JobHandle::new(self, JobKind::GC, id))
+    }
+
+    /// Get gc's schedule.
+    pub async fn get_gc_schedule(&self) -> Result<GCSchedule> {
+        let path = "/system/gc/schedule";
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&reqwest::StatusCode::OK) {
+            Ok(resp.json::<GCSchedule>().await?)
+        } else {
+            Err(anyhow!("failed to get gc schedule: {}", resp.text().await?))
+        }
+    }
+
+    /// Update gc's schedule.
+    pub async fn update_gc_schedule(&self, schedule: &Schedule, parameters: Option<&GCParameters>) -> Result<()> {
+        schedule.validate()?;
+        let path = "/system/gc/schedule";
+        let payload = GCSchedule::new(schedule, parameters);
+        let resp = self.build_request(Method::PUT, path)
+            .json(&payload)
+            .send()
+            .await?;
+        if resp.status().eq(&reqwest::StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to update gc schedule: {}", resp.text().await?))
+        }
+    }
+
     /// Get gc results.
     pub async fn list_gc_results(&self) -> Result<Vec<GCResult>> {
         let path = "/system/gc";
@@ -75,4 +265,58 @@ impl Client {
         let resp = self.build_request(Method::GET, path).send().await?;
         Ok(resp.json::<GCResult>().await?)
     }
+
+    /// Get gc job log.
+    pub async fn get_gc_log(&self, id: u64) -> Result<String> {
+        let path = format!("/system/gc/{}/log", id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&reqwest::StatusCode::OK) {
+            Ok(resp.text().await?)
+        } else {
+            Err(anyhow!("failed to get gc log: {}", resp.text().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_cron, GCParameters, GCSchedule, Schedule, ScheduleType};
+
+    #[test]
+    fn test_validate_cron() {
+        let valid = vec!["0 0 * * * *", "0 */5 * * * ?", "0 0 2 * * 1-5", "0 30 1 1,15 JAN-JUN *", "0 0 0 * * mon-fri"];
+        for cron in valid {
+            assert!(validate_cron(cron).is_ok(), "{}", cron);
+        }
+        let invalid = vec!["", "0 0 * * *", "60 0 * * * *", "0 0 24 * * *", "0 */0 * * * *", "0 0 * * * 7",
+            "0 0 ABC * * *", "0 0 MON * * *", "0 0 0 * FOO *", "0 0 5-2 * * *", "0 0 0 * DEC-JAN *"];
+        for cron in invalid {
+            assert!(validate_cron(cron).is_err(), "{}", cron);
+        }
+    }
+
+    #[test]
+    fn test_schedule_serde() {
+        let schedule = Schedule::new(ScheduleType::Daily, Some(String::from("0 0 0 * * *")));
+        let s = serde_json::to_string(&schedule).unwrap();
+        assert_eq!(s, r#"{"type":"Daily","cron":"0 0 0 * * *"}"#);
+        assert!(Schedule::new(ScheduleType::Custom, None).validate().is_err());
+        let schedule: Schedule = serde_json::from_str(r#"{"type":"Schedule","cron":"0 0 2 * * *"}"#).unwrap();
+        assert_eq!(schedule.schedule_type, ScheduleType::Schedule);
+        assert!(schedule.validate().is_ok());
+        let types: Vec<ScheduleType> = serde_json::from_str(r#"["MANUAL", "SCHEDULE", "daily", "Other"]"#).unwrap();
+        assert_eq!(types, vec![ScheduleType::Manual, ScheduleType::Schedule, ScheduleType::Daily, ScheduleType::Unknown]);
+    }
+
+    #[test]
+    fn test_gc_schedule_serde() {
+        let schedule: GCSchedule = serde_json::from_str(r#"{"schedule":{"type":"None"},"parameters":{}}"#).unwrap();
+        let parameters = schedule.parameters.unwrap();
+        assert!(!parameters.dry_run && !parameters.delete_untagged);
+        let payload = GCSchedule::new(&Schedule::manual(), None);
+        assert_eq!(serde_json::to_string(&payload).unwrap(), r#"{"schedule":{"type":"Manual"}}"#);
+        let parameters = GCParameters { dry_run: true, ..Default::default() };
+        let payload = GCSchedule::new(&Schedule::manual(), Some(&parameters));
+        assert_eq!(serde_json::to_value(&payload).unwrap()["parameters"]["dry_run"], true);
+    }
 }
@@ -0,0 +1,93 @@
+use super::client::Client;
+use super::common::{ScopeSelectors, Selector};
+use serde::{Deserialize, Serialize};
+use anyhow::{anyhow, Result};
+use reqwest::{Method, StatusCode};
+
+/// The tag immutability rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImmutableRule {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<i64>,
+    #[serde(default)]
+    pub priority: i64,
+    #[serde(default)]
+    pub disabled: bool,
+    pub action: String,
+    pub template: String,
+    pub tag_selectors: Vec<Selector>,
+    pub scope_selectors: ScopeSelectors,
+}
+
+impl ImmutableRule {
+    /// Make the tags selected by `tag_selector` immutable in the repositories selected by `repo_selector`.
+    ///
+    /// ```rust
+    /// use harbor_rs::harbor::common::Selector;
+    /// use harbor_rs::harbor::immutable::ImmutableRule;
+    ///
+    /// let rule = ImmutableRule::new(Selector::tag_matches("v*"), Selector::repo_matches("**"));
+    /// ```
+    pub fn new(tag_selector: Selector, repo_selector: Selector) -> Self {
+        ImmutableRule {
+            id: None,
+            project_id: None,
+            priority: 0,
+            disabled: false,
+            action: String::from("immutable"),
+            template: String::from("immutable_template"),
+            tag_selectors: vec![tag_selector],
+            scope_selectors: ScopeSelectors {
+                repository: vec![repo_selector],
+            },
+        }
+    }
+}
+
+impl Client {
+    /// List all immutable tag rules of a project.
+    pub async fn list_immutable_rules(&self, project_id: i64) -> Result<Vec<ImmutableRule>> {
+        let path = format!("/projects/{}/immutabletagrules", project_id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<ImmutableRule>>().await?)
+        } else {
+            Err(anyhow!("failed to list immutable rules: {}", resp.text().await?))
+        }
+    }
+
+    /// Add an immutable tag rule to a project.
+    pub async fn create_immutable_rule(&self, project_id: i64, rule: &ImmutableRule) -> Result<()> {
+        let path = format!("/projects/{}/immutabletagrules", project_id);
+        let resp = self.build_request(Method::POST, path).json(rule).send().await?;
+        if resp.status().eq(&StatusCode::CREATED) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to create immutable rule: {}", resp.text().await?))
+        }
+    }
+
+    /// Update an immutable tag rule of a project.
+    pub async fn update_immutable_rule(&self, project_id: i64, rule_id: i64, rule: &ImmutableRule) -> Result<()> {
+        let path = format!("/projects/{}/immutabletagrules/{}", project_id, rule_id);
+        let resp = self.build_request(Method::PUT, path).json(rule).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to update immutable rule: {}", resp.text().await?))
+        }
+    }
+
+    /// Delete an immutable tag rule of a project.
+    pub async fn delete_immutable_rule(&self, project_id: i64, rule_id: i64) -> Result<()> {
+        let path = format!("/projects/{}/immutabletagrules/{}", project_id, rule_id);
+        let resp = self.build_request(Method::DELETE, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to delete immutable rule: {}", resp.text().await?))
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::time::Duration;
+use super::client::Client;
+use futures::stream::{self, Stream};
+use anyhow::{anyhow, Result};
+use reqwest::Response;
+
+/// The status of a long-running job, unified across the job kinds of Harbor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+    Stopped,
+    Unknown(String),
+}
+
+impl JobStatus {
+    /// Parse the status reported by Harbor, e.g. `finished`, `Succeed` or `InProgress`.
+    pub fn parse(status: &str) -> Self {
+        match status.to_lowercase().as_str() {
+            "pending" | "scheduled" => JobStatus::Pending,
+            "running" | "inprogress" | "in_progress" | "ongoing" => JobStatus::Running,
+            "finished" | "success" | "succeed" | "succeeded" => JobStatus::Succeeded,
+            "error" | "failed" | "fail" => JobStatus::Failed,
+            "stopped" | "stop" | "canceled" | "cancelled" => JobStatus::Stopped,
+            _ => JobStatus::Unknown(status.to_string()),
+        }
+    }
+
+    /// Whether the job will not change any more.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, JobStatus::Succeeded | JobStatus::Failed | JobStatus::Stopped)
+    }
+}
+
+/// The kind of a job, which decides where its status is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobKind {
+    GC,
+    ScanAll,
+    Replication,
+    Retention { retention_id: i64 },
+    Preheat { project_name: String, policy_name: String },
+}
+
+/// The handle of a job triggered through the client.
+///
+/// ```rust,no_run
+/// # async fn run(client: &harbor_rs::Client) -> anyhow::Result<()> {
+/// use std::time::Duration;
+///
+/// let job = client.trigger_gc(None).await?;
+/// let status = job.wait(Duration::from_secs(600)).await?;
+/// println!("gc {} {:?}", job.id(), status);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct JobHandle<'a> {
+    client: &'a Client,
+    kind: JobKind,
+    id: i64,
+    poll_interval: Duration,
+}
+
+impl<'a> JobHandle<'a> {
+    pub fn new(client: &'a Client, kind: JobKind, id: i64) -> Self {
+        JobHandle {
+            client,
+            kind,
+            id,
+            poll_interval: Duration::from_secs(2),
+        }
+    }
+
+    /// Set the interval between two status polls, 2 seconds by default.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn kind(&self) -> &JobKind {
+        &self.kind
+    }
+
+    /// Get the current status of the job.
+    pub async fn status(&self) -> Result<JobStatus> {
+        match &self.kind {
+            JobKind::GC => {
+                let result = self.client.get_gc_result(self.id as u64).await?;
+                Ok(JobStatus::parse(&result.job_status))
+            }
+            JobKind::ScanAll => {
+                let metrics = self.client.scan_all_metrics().await?;
+                metrics.job_status(self.id)
+            }
+            JobKind::Replication => {
+                let execution = self.client.get_replication_execution(self.id).await?;
+                Ok(JobStatus::parse(&execution.status))
+            }
+            JobKind::Retention { retention_id } => {
+                let execution = self.client.get_retention_execution(*retention_id, self.id).await?;
+                Ok(JobStatus::parse(&execution.status))
+            }
+            JobKind::Preheat { project_name, policy_name } => {
+                let execution = self.client.get_preheat_execution(project_name, policy_name, self.id).await?;
+                Ok(JobStatus::parse(&execution.status))
+            }
+        }
+    }
+
+    /// Wait until the job is terminal, return an error if it is still not terminal after `timeout`.
+    pub async fn wait(&self, timeout: Duration) -> Result<JobStatus> {
+        let poll = async {
+            loop {
+                let status = self.status().await?;
+                if status.is_terminal() {
+                    return Ok(status);
+                }
+                tokio::time::sleep(self.poll_interval).await;
+            }
+        };
+        match tokio::time::timeout(timeout, poll).await {
+            Ok(status) => status,
+            Err(_) => Err(anyhow!("job {} is still not finished after {:?}", self.id, timeout)),
+        }
+    }
+
+    /// Stream the status of the job whenever it changes, the stream ends once the job is terminal.
+    pub fn status_stream(&self) -> impl Stream<Item = Result<JobStatus>> + '_ {
+        stream::try_unfold(None, move |last: Option<JobStatus>| async move {
+            if matches!(&last, Some(status) if status.is_terminal()) {
+                return Ok(None);
+            }
+            loop {
+                let status = self.status().await?;
+                if last.as_ref() != Some(&status) {
+                    return Ok(Some((status.clone(), Some(status))));
+                }
+                tokio::time::sleep(self.poll_interval).await;
+            }
+        })
+    }
+}
+
+/// Get the job ID from the `Location` header of the trigger response, e.g. `/api/system/gc/12`.
+pub(crate) fn job_id_from_location(resp: &Response) -> Option<i64> {
+    resp.headers()
+        .get(reqwest::header::LOCATION)?
+        .to_str()
+        .ok()?
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()?
+        .parse::<i64>()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JobStatus;
+
+    #[test]
+    fn parse_job_status() {
+        assert_eq!(JobStatus::parse("finished"), JobStatus::Succeeded);
+        assert_eq!(JobStatus::parse("Succeed"), JobStatus::Succeeded);
+        assert_eq!(JobStatus::parse("InProgress"), JobStatus::Running);
+        assert_eq!(JobStatus::parse("error"), JobStatus::Failed);
+        assert_eq!(JobStatus::parse("whatever"), JobStatus::Unknown(String::from("whatever")));
+        assert!(JobStatus::parse("Stopped").is_terminal());
+        assert!(!JobStatus::parse("pending").is_terminal());
+    }
+}
@@ -0,0 +1,198 @@
+use std::fmt;
+use super::client::Client;
+use super::user::User;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use anyhow::{anyhow, Result};
+use reqwest::{Method, StatusCode};
+
+/// The LDAP configurations used to override the system ones when ping.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct LdapConf {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_search_dn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_search_password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_base_dn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_filter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_uid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_scope: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_connection_timeout: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ldap_verify_cert: Option<bool>,
+}
+
+impl fmt::Debug for LdapConf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LdapConf")
+            .field("ldap_url", &self.ldap_url)
+            .field("ldap_search_dn", &self.ldap_search_dn)
+            .field("ldap_search_password", &self.ldap_search_password.as_ref().map(|_| "******"))
+            .field("ldap_base_dn", &self.ldap_base_dn)
+            .field("ldap_filter", &self.ldap_filter)
+            .field("ldap_uid", &self.ldap_uid)
+            .field("ldap_scope", &self.ldap_scope)
+            .field("ldap_connection_timeout", &self.ldap_connection_timeout)
+            .field("ldap_verify_cert", &self.ldap_verify_cert)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LdapUser {
+    #[serde(alias = "ldap_username")]
+    pub username: String,
+    #[serde(default, alias = "ldap_realname")]
+    pub realname: String,
+    #[serde(default, alias = "ldap_email")]
+    pub email: String,
+}
+
+impl LdapUser {
+    /// Convert to the Harbor user, LDAP users have no password in Harbor.
+    ///
+    /// The user is for reading only, e.g. to compare profiles, since `create_user` rejects it;
+    /// import LDAP users with `ldap_import_users`.
+    pub fn to_user(&self) -> User {
+        User::new(self.email.clone(), self.username.clone(), self.realname.clone(), String::new(), None)
+    }
+}
+
+/// The user failed to import from LDAP.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LdapImportFailure {
+    pub uid: String,
+    #[serde(alias = "err_msg")]
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LdapGroup {
+    pub group_name: String,
+    pub ldap_group_dn: String,
+}
+
+impl Client {
+    /// Ping the LDAP service, with the system configurations overridden by `config_override`.
+    pub async fn ldap_ping(&self, config_override: Option<&LdapConf>) -> Result<()> {
+        let path = "/ldap/ping";
+        let mut req = self.build_request(Method::POST, path);
+        if let Some(config) = config_override {
+            req = req.json(config);
+        }
+        let resp = req.send().await?;
+        if !resp.status().eq(&StatusCode::OK) {
+            return Err(anyhow!("failed to ping ldap: {}", resp.text().await?));
+        }
+        // Harbor v2 responds with the ping result
+        let body = resp.text().await?;
+        if let Ok(result) = serde_json::from_str::<Value>(&body) {
+            if result.get("success").and_then(|v| v.as_bool()) == Some(false) {
+                return Err(anyhow!("failed to ping ldap: {}", result["message"]));
+            }
+        }
+        Ok(())
+    }
+
+    /// Search the available LDAP users by username.
+    pub async fn ldap_search_users(&self, username: &str) -> Result<Vec<LdapUser>> {
+        let path = "/ldap/users/search";
+        let params = [("username", username)];
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<LdapUser>>().await?)
+        } else {
+            Err(anyhow!("failed to search ldap users: {}", resp.text().await?))
+        }
+    }
+
+    /// Import the LDAP users to Harbor, and return the users failed to import.
+    pub async fn ldap_import_users(&self, uids: &[&str]) -> Result<Vec<LdapImportFailure>> {
+        let path = "/ldap/users/import";
+        let payload = json!({ "ldap_uid_list": uids });
+        let resp = self.build_request(Method::POST, path).json(&payload).send().await?;
+        match resp.status() {
+            StatusCode::OK => Ok(vec![]),
+            StatusCode::NOT_FOUND => Ok(resp.json::<Vec<LdapImportFailure>>().await?),
+            _ => Err(anyhow!("failed to import ldap users: {}", resp.text().await?)),
+        }
+    }
+
+    /// Search the available LDAP groups by group name or group DN.
+    pub async fn ldap_search_groups(&self, groupname: Option<&str>, groupdn: Option<&str>) -> Result<Vec<LdapGroup>> {
+        let path = "/ldap/groups/search";
+        let mut params = Vec::new();
+        if let Some(groupname) = groupname {
+            params.push(("groupname", groupname));
+        }
+        if let Some(groupdn) = groupdn {
+            params.push(("groupdn", groupdn));
+        }
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<LdapGroup>>().await?)
+        } else {
+            Err(anyhow!("failed to search ldap groups: {}", resp.text().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LdapConf, LdapImportFailure, LdapUser};
+    use crate::Client;
+
+    #[test]
+    fn parse_ldap_user() {
+        let s = r#"[
+            {"ldap_username": "alice", "ldap_realname": "Alice", "ldap_email": "alice@example.com"},
+            {"username": "bob", "realname": "Bob", "email": "bob@example.com"}
+        ]"#;
+        let users: Vec<LdapUser> = serde_json::from_str(s).unwrap();
+        assert_eq!(users[0].username, "alice");
+        assert_eq!(users[0].email, "alice@example.com");
+        assert_eq!(users[1].realname, "Bob");
+        let user = users[1].to_user();
+        assert_eq!(user.username, "bob");
+        assert_eq!(user.email, "bob@example.com");
+    }
+
+    #[test]
+    fn parse_ldap_import_failure() {
+        let s = r#"[{"uid": "carol", "error": "failed to search ldap user"}]"#;
+        let failures: Vec<LdapImportFailure> = serde_json::from_str(s).unwrap();
+        assert_eq!(failures[0].uid, "carol");
+        assert_eq!(failures[0].error, "failed to search ldap user");
+        let v1: Vec<LdapImportFailure> = serde_json::from_str(r#"[{"uid": "dave", "err_msg": "unknown user"}]"#).unwrap();
+        assert_eq!(v1[0].error, "unknown user");
+    }
+
+    #[test]
+    fn ldap_conf_debug() {
+        let conf = LdapConf {
+            ldap_search_password: Some(String::from("Passw0rd")),
+            ..Default::default()
+        };
+        assert!(!format!("{:?}", conf).contains("Passw0rd"));
+    }
+
+    #[tokio::test]
+    async fn create_ldap_user() {
+        // the request is rejected before sent, so the server is never reached
+        let client = Client::new(String::from("http://127.0.0.1:1/api"), String::new(), String::new()).unwrap();
+        let user = LdapUser {
+            username: String::from("alice"),
+            realname: String::from("Alice"),
+            email: String::from("alice@example.com"),
+        };
+        let err = client.create_user(&user.to_user()).await.unwrap_err();
+        assert!(err.to_string().contains("ldap_import_users"), "{}", err);
+    }
+}
@@ -4,3 +4,25 @@ pub mod common;
 pub mod gc;
 pub mod user;
 pub mod project;
+pub mod immutable;
+pub mod retention;
+pub mod audit;
+pub mod job;
+pub mod scan;
+pub mod system;
+pub mod configuration;
+pub mod ldap;
+pub mod usergroup;
+pub mod endpoint;
+pub mod replication;
+pub mod robot;
+pub mod provision;
+pub mod permission;
+pub mod chart;
+pub mod registry;
+pub mod search;
+pub mod scanner;
+pub mod preheat;
+pub mod reference;
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,310 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use super::client::Client;
+use super::user::Permission;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use anyhow::{anyhow, Result};
+use reqwest::{Method, StatusCode};
+
+/// The resources of the RBAC of Harbor, the unknown ones are kept in `Other`.
+///
+/// The equality is of the string values, so `Other` equals the known variant of the same value.
+#[derive(Debug, Clone)]
+pub enum Resource {
+    Artifact,
+    ArtifactLabel,
+    Configuration,
+    HelmChart,
+    HelmChartVersion,
+    HelmChartVersionLabel,
+    ImmutableTag,
+    Label,
+    Log,
+    Member,
+    Metadata,
+    NotificationPolicy,
+    Project,
+    Quota,
+    Repository,
+    RepositoryLabel,
+    RepositoryTag,
+    RepositoryTagLabel,
+    RepositoryTagManifest,
+    RepositoryTagScanJob,
+    RepositoryTagVulnerability,
+    Robot,
+    Scan,
+    Scanner,
+    Tag,
+    TagRetention,
+    Other(String),
+}
+
+impl Resource {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Resource::Artifact => "artifact",
+            Resource::ArtifactLabel => "artifact-label",
+            Resource::Configuration => "configuration",
+            Resource::HelmChart => "helm-chart",
+            Resource::HelmChartVersion => "helm-chart-version",
+            Resource::HelmChartVersionLabel => "helm-chart-version-label",
+            Resource::ImmutableTag => "immutable-tag",
+            Resource::Label => "label",
+            Resource::Log => "log",
+            Resource::Member => "member",
+            Resource::Metadata => "metadata",
+            Resource::NotificationPolicy => "notification-policy",
+            Resource::Project => "project",
+            Resource::Quota => "quota",
+            Resource::Repository => "repository",
+            Resource::RepositoryLabel => "repository-label",
+            Resource::RepositoryTag => "repository-tag",
+            Resource::RepositoryTagLabel => "repository-tag-label",
+            Resource::RepositoryTagManifest => "repository-tag-manifest",
+            Resource::RepositoryTagScanJob => "repository-tag-scan-job",
+            Resource::RepositoryTagVulnerability => "repository-tag-vulnerability",
+            Resource::Robot => "robot",
+            Resource::Scan => "scan",
+            Resource::Scanner => "scanner",
+            Resource::Tag => "tag",
+            Resource::TagRetention => "tag-retention",
+            Resource::Other(s) => s,
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "artifact" => Resource::Artifact,
+            "artifact-label" => Resource::ArtifactLabel,
+            "configuration" => Resource::Configuration,
+            "helm-chart" => Resource::HelmChart,
+            "helm-chart-version" => Resource::HelmChartVersion,
+            "helm-chart-version-label" => Resource::HelmChartVersionLabel,
+            "immutable-tag" => Resource::ImmutableTag,
+            "label" => Resource::Label,
+            "log" => Resource::Log,
+            "member" => Resource::Member,
+            "metadata" => Resource::Metadata,
+            "notification-policy" => Resource::NotificationPolicy,
+            "project" => Resource::Project,
+            "quota" => Resource::Quota,
+            "repository" => Resource::Repository,
+            "repository-label" => Resource::RepositoryLabel,
+            "repository-tag" => Resource::RepositoryTag,
+            "repository-tag-label" => Resource::RepositoryTagLabel,
+            "repository-tag-manifest" => Resource::RepositoryTagManifest,
+            "repository-tag-scan-job" => Resource::RepositoryTagScanJob,
+            "repository-tag-vulnerability" => Resource::RepositoryTagVulnerability,
+            "robot" => Resource::Robot,
+            "scan" => Resource::Scan,
+            "scanner" => Resource::Scanner,
+            "tag" => Resource::Tag,
+            "tag-retention" => Resource::TagRetention,
+            _ => Resource::Other(s.to_string()),
+        }
+    }
+}
+
+impl PartialEq for Resource {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Resource {}
+
+impl Hash for Resource {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl Serialize for Resource {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Resource {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        Ok(Resource::parse(&String::deserialize(deserializer)?))
+    }
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The actions of the RBAC of Harbor, the unknown ones are kept in `Other`.
+///
+/// Compared by the string values, as `Resource` is.
+#[derive(Debug, Clone)]
+pub enum Action {
+    Create,
+    Delete,
+    List,
+    Operate,
+    Pull,
+    Push,
+    Read,
+    ScannerPull,
+    Stop,
+    Update,
+    Other(String),
+}
+
+impl Action {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Action::Create => "create",
+            Action::Delete => "delete",
+            Action::List => "list",
+            Action::Operate => "operate",
+            Action::Pull => "pull",
+            Action::Push => "push",
+            Action::Read => "read",
+            Action::ScannerPull => "scanner-pull",
+            Action::Stop => "stop",
+            Action::Update => "update",
+            Action::Other(s) => s,
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "create" => Action::Create,
+            "delete" => Action::Delete,
+            "list" => Action::List,
+            "operate" => Action::Operate,
+            "pull" => Action::Pull,
+            "push" => Action::Push,
+            "read" => Action::Read,
+            "scanner-pull" => Action::ScannerPull,
+            "stop" => Action::Stop,
+            "update" => Action::Update,
+            _ => Action::Other(s.to_string()),
+        }
+    }
+}
+
+impl PartialEq for Action {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Action {}
+
+impl Hash for Action {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl Serialize for Action {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Action {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        Ok(Action::parse(&String::deserialize(deserializer)?))
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Permission {
+    /// The typed resource, it is relative to the scope only if listed with `relative`.
+    pub fn resource(&self) -> Resource {
+        Resource::parse(&self.resource)
+    }
+
+    pub fn action(&self) -> Action {
+        Action::parse(&self.action)
+    }
+}
+
+/// The scope of permissions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionScope {
+    System,
+    Project(i64),
+}
+
+impl fmt::Display for PermissionScope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PermissionScope::System => write!(f, "/"),
+            PermissionScope::Project(id) => write!(f, "/project/{}", id),
+        }
+    }
+}
+
+impl Client {
+    /// Get the permissions of the current user in the scope,
+    /// the resources are relative to the scope if `relative` is true, e.g. `repository` instead of `/project/1/repository`.
+    pub async fn list_permissions(&self, scope: PermissionScope, relative: bool) -> Result<Vec<Permission>> {
+        let path = "/users/current/permissions";
+        let params = [("scope", scope.to_string()), ("relative", relative.to_string())];
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<Permission>>().await?)
+        } else {
+            Err(anyhow!("failed to list permissions: {}", resp.text().await?))
+        }
+    }
+
+    /// Check whether the current user can do the action on the resource,
+    /// of the project if `project_id` is set, or of the system.
+    ///
+    /// ```rust,no_run
+    /// # async fn run(client: &harbor_rs::Client) -> anyhow::Result<()> {
+    /// use harbor_rs::harbor::permission::{Action, Resource};
+    ///
+    /// if !client.can(Resource::RepositoryTag, Action::Delete, Some(1)).await? {
+    ///     anyhow::bail!("not allowed to delete tags");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn can(&self, resource: Resource, action: Action, project_id: Option<i64>) -> Result<bool> {
+        let scope = match project_id {
+            Some(project_id) => PermissionScope::Project(project_id),
+            None => PermissionScope::System,
+        };
+        let permissions = self.list_permissions(scope, true).await?;
+        Ok(permissions.iter().any(|p| p.resource() == resource && p.action() == action))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, PermissionScope, Resource};
+    use crate::harbor::user::Permission;
+
+    #[test]
+    fn typed_permission() {
+        let permission = Permission {
+            action: String::from("delete"),
+            resource: String::from("repository-tag"),
+        };
+        assert_eq!(permission.resource(), Resource::RepositoryTag);
+        assert_eq!(permission.action(), Action::Delete);
+        assert_eq!(Resource::parse("preheat-policy"), Resource::Other(String::from("preheat-policy")));
+        assert_eq!(Resource::parse("preheat-policy").to_string(), "preheat-policy");
+        assert_eq!(PermissionScope::Project(3).to_string(), "/project/3");
+        assert_eq!(Resource::Other(String::from("artifact")), Resource::Artifact);
+        assert_eq!(Action::Other(String::from("pull")), Action::Pull);
+        let resources: std::collections::HashSet<Resource> = vec![Resource::Artifact, Resource::Other(String::from("artifact"))].into_iter().collect();
+        assert_eq!(resources.len(), 1);
+    }
+}
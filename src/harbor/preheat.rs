@@ -0,0 +1,452 @@
+use std::collections::HashMap;
+use std::fmt;
+use super::client::Client;
+use super::job::{job_id_from_location, JobHandle, JobKind};
+use super::replication::{TriggerSettings, TriggerType};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use anyhow::{anyhow, Result};
+use reqwest::{Method, StatusCode};
+
+/// The preheat provider type, e.g. Dragonfly or Kraken.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PreheatProviderMetadata {
+    pub id: String,
+    pub name: String,
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub maintainers: Vec<String>,
+    pub version: Option<String>,
+    pub source: Option<String>,
+}
+
+/// The preheat provider instance.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PreheatInstance {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// The ID of the provider type, e.g. `dragonfly`.
+    pub vendor: String,
+    pub endpoint: String,
+    /// The auth mode, e.g. `NONE`, `BASIC`, `OAUTH` or `CUSTOM`.
+    pub auth_mode: String,
+    /// The auth credentials, e.g. `username` and `password` for the `BASIC` auth mode.
+    #[serde(default)]
+    pub auth_info: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub default: bool,
+    #[serde(default)]
+    pub insecure: bool,
+}
+
+impl fmt::Debug for PreheatInstance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let auth_info: HashMap<&String, &str> = self.auth_info.keys().map(|k| (k, "******")).collect();
+        f.debug_struct("PreheatInstance")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("vendor", &self.vendor)
+            .field("endpoint", &self.endpoint)
+            .field("auth_mode", &self.auth_mode)
+            .field("auth_info", &auth_info)
+            .field("status", &self.status)
+            .field("enabled", &self.enabled)
+            .field("default", &self.default)
+            .field("insecure", &self.insecure)
+            .finish()
+    }
+}
+
+/// The filter of the artifacts to preheat, repository and tag support doublestar patterns.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum PreheatFilter {
+    Repository(String),
+    Tag(String),
+    Label(String),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PreheatTrigger {
+    #[serde(rename = "type")]
+    pub trigger_type: TriggerType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_setting: Option<TriggerSettings>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct RawPreheatPolicy {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<i64>,
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    project_id: i64,
+    provider_id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider_name: Option<String>,
+    /// The JSON encoded filters.
+    filters: String,
+    /// The JSON encoded trigger.
+    trigger: String,
+    #[serde(default)]
+    enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    creation_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    update_time: Option<String>,
+}
+
+/// The preheat policy of a project.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(try_from = "RawPreheatPolicy", into = "RawPreheatPolicy")]
+pub struct PreheatPolicy {
+    pub id: Option<i64>,
+    pub name: String,
+    pub description: String,
+    pub project_id: i64,
+    /// The ID of the preheat instance.
+    pub provider_id: i64,
+    pub provider_name: Option<String>,
+    pub filters: Vec<PreheatFilter>,
+    pub trigger: PreheatTrigger,
+    pub enabled: bool,
+    pub creation_time: Option<String>,
+    pub update_time: Option<String>,
+}
+
+impl PreheatPolicy {
+    pub fn new(name: String, project_id: i64, provider_id: i64, filters: Vec<PreheatFilter>, trigger: PreheatTrigger) -> Self {
+        PreheatPolicy {
+            id: None,
+            name,
+            description: String::new(),
+            project_id,
+            provider_id,
+            provider_name: None,
+            filters,
+            trigger,
+            enabled: true,
+            creation_time: None,
+            update_time: None,
+        }
+    }
+}
+
+impl TryFrom<RawPreheatPolicy> for PreheatPolicy {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: RawPreheatPolicy) -> Result<Self> {
+        Ok(PreheatPolicy {
+            id: raw.id,
+            name: raw.name,
+            description: raw.description,
+            project_id: raw.project_id,
+            provider_id: raw.provider_id,
+            provider_name: raw.provider_name,
+            filters: serde_json::from_str(&raw.filters)?,
+            trigger: serde_json::from_str(&raw.trigger)?,
+            enabled: raw.enabled,
+            creation_time: raw.creation_time,
+            update_time: raw.update_time,
+        })
+    }
+}
+
+impl From<PreheatPolicy> for RawPreheatPolicy {
+    fn from(policy: PreheatPolicy) -> Self {
+        RawPreheatPolicy {
+            id: policy.id,
+            name: policy.name,
+            description: policy.description,
+            project_id: policy.project_id,
+            provider_id: policy.provider_id,
+            provider_name: policy.provider_name,
+            // serializing the derived types never fails
+            filters: serde_json::to_string(&policy.filters).unwrap_or_default(),
+            trigger: serde_json::to_string(&policy.trigger).unwrap_or_default(),
+            enabled: policy.enabled,
+            creation_time: policy.creation_time,
+            update_time: policy.update_time,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PreheatExecution {
+    pub id: i64,
+    /// The status of the execution, e.g. `Running`, `Success`, `Error` or `Stopped`.
+    pub status: String,
+    pub status_message: Option<String>,
+    pub trigger: String,
+    /// The task counts by status.
+    pub metrics: Option<HashMap<String, i64>>,
+    pub start_time: String,
+    pub end_time: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PreheatTask {
+    pub id: i64,
+    pub execution_id: i64,
+    pub status: String,
+    pub status_message: Option<String>,
+    #[serde(default)]
+    pub run_count: i64,
+    /// The artifact and provider of the task.
+    pub extra_attrs: Option<HashMap<String, Value>>,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+}
+
+impl Client {
+    /// List the preheat provider types.
+    pub async fn list_preheat_providers(&self) -> Result<Vec<PreheatProviderMetadata>> {
+        let path = "/p2p/preheat/providers";
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<PreheatProviderMetadata>>().await?)
+        } else {
+            Err(anyhow!("failed to list preheat providers: {}", resp.text().await?))
+        }
+    }
+
+    /// List the preheat provider instances.
+    pub async fn list_preheat_instances(&self, page: Option<u32>, page_size: Option<u32>) -> Result<Vec<PreheatInstance>> {
+        let path = "/p2p/preheat/instances";
+        let mut params = Vec::new();
+        if let Some(page) = page {
+            params.push(("page", page.to_string()));
+        }
+        if let Some(page_size) = page_size {
+            params.push(("page_size", page_size.to_string()));
+        }
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<PreheatInstance>>().await?)
+        } else {
+            Err(anyhow!("failed to list preheat instances: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the preheat provider instance by name.
+    pub async fn get_preheat_instance(&self, name: &str) -> Result<PreheatInstance> {
+        let path = format!("/p2p/preheat/instances/{}", name);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<PreheatInstance>().await?)
+        } else {
+            Err(anyhow!("failed to get preheat instance: {}", resp.text().await?))
+        }
+    }
+
+    /// Create a preheat provider instance.
+    pub async fn create_preheat_instance(&self, instance: &PreheatInstance) -> Result<()> {
+        let path = "/p2p/preheat/instances";
+        let resp = self.build_request(Method::POST, path).json(instance).send().await?;
+        if resp.status().eq(&StatusCode::CREATED) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to create preheat instance: {}", resp.text().await?))
+        }
+    }
+
+    /// Update the preheat provider instance.
+    pub async fn update_preheat_instance(&self, name: &str, instance: &PreheatInstance) -> Result<()> {
+        let path = format!("/p2p/preheat/instances/{}", name);
+        let resp = self.build_request(Method::PUT, path).json(instance).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to update preheat instance: {}", resp.text().await?))
+        }
+    }
+
+    /// Delete the preheat provider instance.
+    pub async fn delete_preheat_instance(&self, name: &str) -> Result<()> {
+        let path = format!("/p2p/preheat/instances/{}", name);
+        let resp = self.build_request(Method::DELETE, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to delete preheat instance: {}", resp.text().await?))
+        }
+    }
+
+    /// Check the connectivity of the preheat provider instance.
+    pub async fn ping_preheat_instance(&self, instance: &PreheatInstance) -> Result<()> {
+        let path = "/p2p/preheat/instances/ping";
+        let resp = self.build_request(Method::POST, path).json(instance).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to ping preheat instance: {}", resp.text().await?))
+        }
+    }
+
+    /// List the preheat policies of the project.
+    pub async fn list_preheat_policies(&self, project_name: &str) -> Result<Vec<PreheatPolicy>> {
+        let path = format!("/projects/{}/preheat/policies", project_name);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<PreheatPolicy>>().await?)
+        } else {
+            Err(anyhow!("failed to list preheat policies: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the preheat policy of the project.
+    pub async fn get_preheat_policy(&self, project_name: &str, policy_name: &str) -> Result<PreheatPolicy> {
+        let path = format!("/projects/{}/preheat/policies/{}", project_name, policy_name);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<PreheatPolicy>().await?)
+        } else {
+            Err(anyhow!("failed to get preheat policy: {}", resp.text().await?))
+        }
+    }
+
+    /// Create a preheat policy of the project.
+    pub async fn create_preheat_policy(&self, project_name: &str, policy: &PreheatPolicy) -> Result<()> {
+        let path = format!("/projects/{}/preheat/policies", project_name);
+        let resp = self.build_request(Method::POST, path).json(policy).send().await?;
+        if resp.status().eq(&StatusCode::CREATED) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to create preheat policy: {}", resp.text().await?))
+        }
+    }
+
+    /// Update the preheat policy of the project.
+    pub async fn update_preheat_policy(&self, project_name: &str, policy_name: &str, policy: &PreheatPolicy) -> Result<()> {
+        let path = format!("/projects/{}/preheat/policies/{}", project_name, policy_name);
+        let resp = self.build_request(Method::PUT, path).json(policy).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to update preheat policy: {}", resp.text().await?))
+        }
+    }
+
+    /// Delete the preheat policy of the project.
+    pub async fn delete_preheat_policy(&self, project_name: &str, policy_name: &str) -> Result<()> {
+        let path = format!("/projects/{}/preheat/policies/{}", project_name, policy_name);
+        let resp = self.build_request(Method::DELETE, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to delete preheat policy: {}", resp.text().await?))
+        }
+    }
+
+    /// Preheat manually with the policy, and return the handle of the execution.
+    pub async fn trigger_preheat(&self, project_name: &str, policy_name: &str) -> Result<JobHandle<'_>> {
+        let policy = self.get_preheat_policy(project_name, policy_name).await?;
+        let last_id = self.latest_preheat_execution_id(project_name, policy_name).await?;
+        let path = format!("/projects/{}/preheat/policies/{}", project_name, policy_name);
+        let payload = serde_json::json!({ "policy_id": policy.id });
+        let resp = self.build_request(Method::POST, path).json(&payload).send().await?;
+        if !resp.status().eq(&StatusCode::CREATED) {
+            return Err(anyhow!("failed to trigger preheat: {}", resp.text().await?));
+        }
+        let id = match job_id_from_location(&resp) {
+            Some(id) => id,
+            // the first execution created after the trigger
+            None => self.list_preheat_executions(project_name, policy_name, None, Some(100)).await?
+                .iter()
+                .map(|e| e.id)
+                .filter(|id| *id > last_id)
+                .min()
+                .ok_or_else(|| anyhow!("failed to find the triggered preheat execution"))?,
+        };
+        let kind = JobKind::Preheat {
+            project_name: project_name.to_string(),
+            policy_name: policy_name.to_string(),
+        };
+        Ok(JobHandle::new(self, kind, id))
+    }
+
+    /// The ID of the latest execution of the preheat policy, 0 if none, the executions are ordered by ID desc.
+    async fn latest_preheat_execution_id(&self, project_name: &str, policy_name: &str) -> Result<i64> {
+        let executions = self.list_preheat_executions(project_name, policy_name, None, Some(1)).await?;
+        Ok(executions.first().map(|e| e.id).unwrap_or(0))
+    }
+
+    /// List the executions of the preheat policy.
+    pub async fn list_preheat_executions(&self, project_name: &str, policy_name: &str, page: Option<u32>, page_size: Option<u32>) -> Result<Vec<PreheatExecution>> {
+        let path = format!("/projects/{}/preheat/policies/{}/executions", project_name, policy_name);
+        let mut params = Vec::new();
+        if let Some(page) = page {
+            params.push(("page", page.to_string()));
+        }
+        if let Some(page_size) = page_size {
+            params.push(("page_size", page_size.to_string()));
+        }
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<PreheatExecution>>().await?)
+        } else {
+            Err(anyhow!("failed to list preheat executions: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the execution of the preheat policy.
+    pub async fn get_preheat_execution(&self, project_name: &str, policy_name: &str, execution_id: i64) -> Result<PreheatExecution> {
+        let path = format!("/projects/{}/preheat/policies/{}/executions/{}", project_name, policy_name, execution_id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<PreheatExecution>().await?)
+        } else {
+            Err(anyhow!("failed to get preheat execution: {}", resp.text().await?))
+        }
+    }
+
+    /// List the tasks of the preheat execution.
+    pub async fn list_preheat_tasks(&self, project_name: &str, policy_name: &str, execution_id: i64) -> Result<Vec<PreheatTask>> {
+        let path = format!("/projects/{}/preheat/policies/{}/executions/{}/tasks", project_name, policy_name, execution_id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<PreheatTask>>().await?)
+        } else {
+            Err(anyhow!("failed to list preheat tasks: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the log of the preheat task.
+    pub async fn get_preheat_task_log(&self, project_name: &str, policy_name: &str, execution_id: i64, task_id: i64) -> Result<String> {
+        let path = format!("/projects/{}/preheat/policies/{}/executions/{}/tasks/{}/logs", project_name, policy_name, execution_id, task_id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.text().await?)
+        } else {
+            Err(anyhow!("failed to get preheat task log: {}", resp.text().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PreheatFilter, PreheatPolicy, PreheatTrigger};
+    use crate::harbor::replication::TriggerType;
+
+    #[test]
+    fn preheat_policy_serde() {
+        let trigger = PreheatTrigger { trigger_type: TriggerType::EventBased, trigger_setting: None };
+        let filters = vec![PreheatFilter::Repository(String::from("**")), PreheatFilter::Tag(String::from("v*"))];
+        let policy = PreheatPolicy::new(String::from("release"), 1, 2, filters.clone(), trigger);
+        let value = serde_json::to_value(&policy).unwrap();
+        assert_eq!(value["filters"], r#"[{"type":"repository","value":"**"},{"type":"tag","value":"v*"}]"#);
+        assert_eq!(value["trigger"], r#"{"type":"event_based"}"#);
+        let parsed: PreheatPolicy = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.filters, filters);
+    }
+}
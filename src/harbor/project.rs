@@ -1,6 +1,7 @@
 use serde_json::Value;
 use serde::{Deserialize, Serialize};
 use crate::Client;
+use crate::harbor::usergroup::UserGroup;
 use anyhow::Result;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +43,102 @@ pub struct WebhookJob {
     pub policy_id: i64,
 }
 
+/// The webhook event types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WebhookEventType {
+    PushArtifact,
+    PullArtifact,
+    DeleteArtifact,
+    UploadChart,
+    DownloadChart,
+    DeleteChart,
+    ScanningCompleted,
+    ScanningFailed,
+    ScanningStopped,
+    QuotaExceed,
+    QuotaWarning,
+    Replication,
+    TagRetention,
+}
+
+/// The webhook policy target.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookTarget {
+    /// The webhook target notify type, e.g. `http`.
+    #[serde(rename = "type")]
+    pub notify_type: String,
+    /// The webhook target address.
+    pub address: String,
+    /// The webhook auth header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_header: Option<String>,
+    /// Whether or not to skip cert verify.
+    #[serde(default)]
+    pub skip_cert_verify: bool,
+}
+
+/// The webhook policy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookPolicy {
+    /// The webhook policy name.
+    pub name: String,
+    /// The webhook policy description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The targets of the webhook policy.
+    pub targets: Vec<WebhookTarget>,
+    /// The event types of the webhook policy.
+    pub event_types: Vec<WebhookEventType>,
+    /// Whether the webhook policy is enabled or not.
+    pub enabled: bool,
+    /// The webhook policy ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    /// The project ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<i64>,
+    /// The creator of the webhook policy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+    /// The webhook policy creation time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creation_time: Option<String>,
+    /// The webhook policy update time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_time: Option<String>,
+}
+
+/// The webhook policy and last trigger time group by event type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookLastTrigger {
+    /// The webhook event type.
+    pub event_type: WebhookEventType,
+    /// Whether or not the webhook policy enabled.
+    pub enabled: bool,
+    /// The creation time of webhook policy.
+    pub creation_time: String,
+    /// The last trigger time of webhook policy.
+    pub last_trigger_time: Option<String>,
+}
+
+impl WebhookPolicy {
+    pub fn new(name: String, targets: Vec<WebhookTarget>, event_types: Vec<WebhookEventType>) -> Self {
+        WebhookPolicy {
+            name,
+            description: None,
+            targets,
+            event_types,
+            enabled: true,
+            id: None,
+            project_id: None,
+            creator: None,
+            creation_time: None,
+            update_time: None,
+        }
+    }
+}
+
 impl Client {
     /// List projects
     pub async fn list_projects(&self, name: Option<String>, public: Option<bool>, owner: Option<String>, page: Option<u32>, page_size: Option<u32>) -> Result<Vec<Project>> {
@@ -74,6 +171,17 @@ impl Client {
         }
     }
 
+    /// Return specific project detail information
+    pub async fn get_project(&self, id: i64) -> Result<Project> {
+        let path = format!("/projects/{}", id);
+        let resp = self.build_request(reqwest::Method::GET, path).send().await?;
+        if resp.status() != reqwest::StatusCode::OK {
+            return Err(anyhow::anyhow!("failed to get project: {}", resp.text().await?));
+        }
+        let project = resp.json::<Project>().await?;
+        Ok(project)
+    }
+
     /// List project webhook jobs
     pub async fn list_webhook_jobs(&self, project_id: i64, policy_id: i64) -> Result<Vec<WebhookJob>> {
         let path = format!("/projects/{}/webhook/jobs", project_id);
@@ -86,6 +194,79 @@ impl Client {
         Ok(webhook_jobs)
     }
 
+    /// List project webhook policies
+    pub async fn list_webhook_policies(&self, project_id: i64) -> Result<Vec<WebhookPolicy>> {
+        let path = format!("/projects/{}/webhook/policies", project_id);
+        let resp = self.build_request(reqwest::Method::GET, path).send().await?;
+        if resp.status() != reqwest::StatusCode::OK {
+            return Err(anyhow::anyhow!("failed to list webhook policies: {}", resp.text().await?));
+        }
+        let policies = resp.json::<Vec<WebhookPolicy>>().await?;
+        Ok(policies)
+    }
+
+    /// Create project webhook policy
+    pub async fn create_webhook_policy(&self, project_id: i64, policy: &WebhookPolicy) -> Result<()> {
+        let path = format!("/projects/{}/webhook/policies", project_id);
+        let resp = self.build_request(reqwest::Method::POST, path).json(policy).send().await?;
+        if resp.status() != reqwest::StatusCode::CREATED {
+            return Err(anyhow::anyhow!("failed to create webhook policy: {}", resp.text().await?));
+        }
+        Ok(())
+    }
+
+    /// Update webhook policy of a project
+    pub async fn update_webhook_policy(&self, project_id: i64, policy_id: i64, policy: &WebhookPolicy) -> Result<()> {
+        let path = format!("/projects/{}/webhook/policies/{}", project_id, policy_id);
+        let resp = self.build_request(reqwest::Method::PUT, path).json(policy).send().await?;
+        if resp.status() != reqwest::StatusCode::OK {
+            return Err(anyhow::anyhow!("failed to update webhook policy: {}", resp.text().await?));
+        }
+        Ok(())
+    }
+
+    /// Delete webhook policy of a project
+    pub async fn delete_webhook_policy(&self, project_id: i64, policy_id: i64) -> Result<()> {
+        let path = format!("/projects/{}/webhook/policies/{}", project_id, policy_id);
+        let resp = self.build_request(reqwest::Method::DELETE, path).send().await?;
+        if resp.status() != reqwest::StatusCode::OK {
+            return Err(anyhow::anyhow!("failed to delete webhook policy: {}", resp.text().await?));
+        }
+        Ok(())
+    }
+
+    /// Get project webhook policy last trigger info
+    pub async fn get_webhook_last_trigger(&self, project_id: i64) -> Result<Vec<WebhookLastTrigger>> {
+        let path = format!("/projects/{}/webhook/lasttrigger", project_id);
+        let resp = self.build_request(reqwest::Method::GET, path).send().await?;
+        if resp.status() != reqwest::StatusCode::OK {
+            return Err(anyhow::anyhow!("failed to get webhook last trigger: {}", resp.text().await?));
+        }
+        let last_triggers = resp.json::<Vec<WebhookLastTrigger>>().await?;
+        Ok(last_triggers)
+    }
+
+    /// Test project webhook connection
+    pub async fn test_webhook_target(&self, project_id: i64, policy: &WebhookPolicy) -> Result<()> {
+        let path = format!("/projects/{}/webhook/policies/test", project_id);
+        let resp = self.build_request(reqwest::Method::POST, path).json(policy).send().await?;
+        if resp.status() != reqwest::StatusCode::OK {
+            return Err(anyhow::anyhow!("failed to test webhook target: {}", resp.text().await?));
+        }
+        Ok(())
+    }
+
+    /// Add a user group as a member of the project
+    pub async fn add_project_group_member(&self, project_id: i64, group: &UserGroup, role_id: i64) -> Result<()> {
+        let path = format!("/projects/{}/members", project_id);
+        let payload = serde_json::json!({ "role_id": role_id, "member_group": group });
+        let resp = self.build_request(reqwest::Method::POST, path).json(&payload).send().await?;
+        if resp.status() != reqwest::StatusCode::CREATED {
+            return Err(anyhow::anyhow!("failed to add project group member: {}", resp.text().await?));
+        }
+        Ok(())
+    }
+
     /// Delete project by projectID
     pub async fn delete_project(&self, id: i64) -> Result<()> {
         let path = format!("/projects/{}", id);
@@ -104,4 +285,13 @@ mod tests {
         let client = crate::Client::setup().unwrap();
         client.list_projects(Some(String::from("test")), None, None, None, None).await.unwrap();
     }
+
+    #[test]
+    fn webhook_event_type_serde() {
+        let event_types = vec![super::WebhookEventType::PushArtifact, super::WebhookEventType::ScanningCompleted];
+        let s = serde_json::to_string(&event_types).unwrap();
+        assert_eq!(s, r#"["PUSH_ARTIFACT","SCANNING_COMPLETED"]"#);
+        let parsed: Vec<super::WebhookEventType> = serde_json::from_str(&s).unwrap();
+        assert_eq!(parsed, event_types);
+    }
 }
@@ -0,0 +1,304 @@
+use std::collections::HashSet;
+use std::fmt;
+use super::client::Client;
+use super::user::{User, UserBuilder, UserUpdate};
+use serde::Deserialize;
+use anyhow::{anyhow, Result};
+
+/// The user expected to exist in Harbor.
+#[derive(Clone, Deserialize)]
+pub struct DesiredUser {
+    pub username: String,
+    pub email: String,
+    pub realname: String,
+    #[serde(default)]
+    pub admin: bool,
+    #[serde(default)]
+    pub comment: Option<String>,
+    /// The initial password, only required to create the user.
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+impl fmt::Debug for DesiredUser {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DesiredUser")
+            .field("username", &self.username)
+            .field("email", &self.email)
+            .field("realname", &self.realname)
+            .field("admin", &self.admin)
+            .field("comment", &self.comment)
+            .field("password", &self.password.as_ref().map(|_| "******"))
+            .finish()
+    }
+}
+
+/// Load the desired users from YAML, which is a list of users.
+///
+/// ```rust
+/// let users = harbor_rs::harbor::provision::load_desired_users_yaml(r#"
+/// - username: alice
+///   email: alice@example.com
+///   realname: Alice
+///   admin: true
+/// "#).unwrap();
+/// assert!(users[0].admin);
+/// ```
+pub fn load_desired_users_yaml(s: &str) -> Result<Vec<DesiredUser>> {
+    Ok(serde_yaml::from_str(s)?)
+}
+
+/// Load the desired users from CSV with a header line,
+/// the columns are username, email, realname, admin, comment and password,
+/// the last three are optional.
+pub fn load_desired_users_csv(s: &str) -> Result<Vec<DesiredUser>> {
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(s.as_bytes());
+    let mut users = Vec::new();
+    for record in reader.deserialize::<CsvUser>() {
+        let user = record?;
+        // empty CSV cells mean unset
+        let admin = match user.admin.as_deref() {
+            None | Some("") => false,
+            Some(admin) => admin.parse::<bool>()
+                .map_err(|_| anyhow!("invalid admin {:?} of user {}: expected true or false", admin, user.username))?,
+        };
+        users.push(DesiredUser {
+            username: user.username,
+            email: user.email,
+            realname: user.realname,
+            admin,
+            comment: user.comment.filter(|c| !c.is_empty()),
+            password: user.password.filter(|p| !p.is_empty()),
+        });
+    }
+    Ok(users)
+}
+
+/// The CSV row of a desired user, the cells are kept as is until validated.
+#[derive(Deserialize)]
+struct CsvUser {
+    username: String,
+    email: String,
+    realname: String,
+    #[serde(default)]
+    admin: Option<String>,
+    #[serde(default)]
+    comment: Option<String>,
+    #[serde(default)]
+    password: Option<String>,
+}
+
+/// The change to make a Harbor user match the desired one.
+#[derive(Debug, Clone)]
+pub enum UserAction {
+    Create(DesiredUser),
+    Update { user_id: i64, username: String, update: UserUpdate },
+    SetAdmin { user_id: i64, username: String, admin: bool },
+    Delete { user_id: i64, username: String },
+}
+
+impl UserAction {
+    pub fn username(&self) -> &str {
+        match self {
+            UserAction::Create(user) => &user.username,
+            UserAction::Update { username, .. } => username,
+            UserAction::SetAdmin { username, .. } => username,
+            UserAction::Delete { username, .. } => username,
+        }
+    }
+}
+
+impl fmt::Display for UserAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UserAction::Create(user) => write!(f, "create user {} <{}>", user.username, user.email),
+            UserAction::Update { username, update, .. } => {
+                let mut fields = Vec::new();
+                if let Some(email) = &update.email {
+                    fields.push(format!("email={}", email));
+                }
+                if let Some(realname) = &update.realname {
+                    fields.push(format!("realname={}", realname));
+                }
+                if let Some(comment) = &update.comment {
+                    fields.push(format!("comment={}", comment));
+                }
+                write!(f, "update user {} profile: {}", username, fields.join(", "))
+            }
+            UserAction::SetAdmin { username, admin: true, .. } => write!(f, "grant admin role to user {}", username),
+            UserAction::SetAdmin { username, admin: false, .. } => write!(f, "revoke admin role from user {}", username),
+            UserAction::Delete { username, .. } => write!(f, "delete user {}", username),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionOutcome {
+    /// Not applied because of dry run.
+    Planned,
+    Applied,
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct UserActionResult {
+    pub action: UserAction,
+    pub outcome: ActionOutcome,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReconcileOptions {
+    /// Only compute the actions without applying them.
+    pub dry_run: bool,
+    /// Delete the users not desired, except the built-in admin.
+    pub delete_missing: bool,
+}
+
+/// Compute the actions to make the existing users match the desired ones.
+pub fn plan_users(existing: &[User], desired: &[DesiredUser], delete_missing: bool) -> Vec<UserAction> {
+    let mut actions = Vec::new();
+    for d in desired {
+        let user = match existing.iter().find(|u| u.username == d.username) {
+            Some(user) => user,
+            None => {
+                actions.push(UserAction::Create(d.clone()));
+                continue;
+            }
+        };
+        let user_id = match user.user_id {
+            Some(user_id) => user_id,
+            None => continue,
+        };
+        let update = UserUpdate {
+            email: Some(d.email.clone()).filter(|e| *e != user.email),
+            realname: Some(d.realname.clone()).filter(|r| *r != user.realname),
+            comment: d.comment.clone().filter(|c| Some(c) != user.comment.as_ref()),
+        };
+        if update.email.is_some() || update.realname.is_some() || update.comment.is_some() {
+            actions.push(UserAction::Update { user_id, username: d.username.clone(), update });
+        }
+        if user.has_admin_role.unwrap_or(false) != d.admin {
+            actions.push(UserAction::SetAdmin { user_id, username: d.username.clone(), admin: d.admin });
+        }
+    }
+    if delete_missing {
+        let desired_names: HashSet<&str> = desired.iter().map(|d| d.username.as_str()).collect();
+        for user in existing {
+            match user.user_id {
+                // the built-in admin
+                Some(1) | None => continue,
+                Some(user_id) if !desired_names.contains(user.username.as_str()) => {
+                    actions.push(UserAction::Delete { user_id, username: user.username.clone() });
+                }
+                _ => {}
+            }
+        }
+    }
+    actions
+}
+
+impl Client {
+    /// Get all the registered users of Harbor, page by page.
+    pub async fn list_all_users(&self) -> Result<Vec<User>> {
+        let page_size = 100;
+        let mut users = Vec::new();
+        for page in 1.. {
+            let list = self.list_users(None, None, Some(page), Some(page_size)).await?;
+            let done = (list.len() as u32) < page_size;
+            users.extend(list);
+            if done {
+                break;
+            }
+        }
+        Ok(users)
+    }
+
+    /// Make the Harbor users match the desired ones, and report the result of each action.
+    pub async fn reconcile_users(&self, desired: &[DesiredUser], options: &ReconcileOptions) -> Result<Vec<UserActionResult>> {
+        let existing = self.list_all_users().await?;
+        let actions = plan_users(&existing, desired, options.delete_missing);
+        let mut results = Vec::new();
+        for action in actions {
+            let outcome = if options.dry_run {
+                ActionOutcome::Planned
+            } else {
+                match self.apply_user_action(&action).await {
+                    Ok(_) => ActionOutcome::Applied,
+                    Err(e) => ActionOutcome::Failed(e.to_string()),
+                }
+            };
+            results.push(UserActionResult { action, outcome });
+        }
+        Ok(results)
+    }
+
+    /// Apply a single action computed by `plan_users`.
+    pub async fn apply_user_action(&self, action: &UserAction) -> Result<()> {
+        match action {
+            UserAction::Create(d) => {
+                let password = d.password.as_ref()
+                    .ok_or_else(|| anyhow!("password is required to create user {}", d.username))?;
+                let mut builder = UserBuilder::new(&d.username, &d.email, password).realname(&d.realname);
+                if let Some(comment) = &d.comment {
+                    builder = builder.comment(comment);
+                }
+                self.create_user(&builder.build()).await?;
+                if d.admin {
+                    let user_id = self.list_users(Some(d.username.clone()), None, None, None).await?
+                        .iter()
+                        .find(|u| u.username == d.username)
+                        .and_then(|u| u.user_id)
+                        .ok_or_else(|| anyhow!("user {} not found after created", d.username))?;
+                    self.update_sysadmin(user_id, true).await?;
+                }
+                Ok(())
+            }
+            UserAction::Update { user_id, update, .. } => self.update_user(*user_id, update).await,
+            UserAction::SetAdmin { user_id, admin, .. } => self.update_sysadmin(*user_id, *admin).await,
+            UserAction::Delete { user_id, .. } => self.delete_user(*user_id).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_desired_users_csv, plan_users, UserAction};
+    use crate::harbor::user::User;
+
+    fn existing_user(id: i64, username: &str, email: &str, admin: bool) -> User {
+        let mut user = User::new(email.to_string(), username.to_string(), username.to_string(), String::new(), None);
+        user.user_id = Some(id);
+        user.has_admin_role = Some(admin);
+        user
+    }
+
+    #[test]
+    fn plan_users_actions() {
+        let existing = vec![
+            existing_user(1, "admin", "admin@example.com", true),
+            existing_user(2, "alice", "alice@old.com", false),
+            existing_user(3, "bob", "bob@example.com", false),
+        ];
+        let desired = load_desired_users_csv("username,email,realname,admin,comment,password
+alice,alice@example.com,alice,true,,
+carol,carol@example.com,Carol,false,contractor,Carol1234
+dave,dave@example.com,Dave,,,Dave1234
+").unwrap();
+        assert_eq!(desired[1].password.as_deref(), Some("Carol1234"));
+        assert!(desired[0].comment.is_none());
+        assert!(!desired[2].admin);
+        assert!(load_desired_users_csv("username,email,realname,admin\nerin,erin@example.com,Erin,yes\n").is_err());
+
+        let actions = plan_users(&existing, &desired, true);
+        let s: Vec<String> = actions.iter().map(|a| a.to_string()).collect();
+        assert_eq!(s, vec![
+            "update user alice profile: email=alice@example.com",
+            "grant admin role to user alice",
+            "create user carol <carol@example.com>",
+            "create user dave <dave@example.com>",
+            "delete user bob",
+        ]);
+        assert!(matches!(actions[4], UserAction::Delete { user_id: 3, .. }));
+        assert_eq!(plan_users(&existing, &desired, false).len(), 4);
+    }
+}
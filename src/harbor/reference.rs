@@ -0,0 +1,279 @@
+use std::fmt;
+use std::str::FromStr;
+use super::client::Client;
+use anyhow::{anyhow, Result};
+use reqwest::Url;
+
+/// The maximum length of the repository name including the registry host.
+const NAME_TOTAL_LENGTH_MAX: usize = 255;
+
+/// A reference to an image, e.g. `harbor.example.com/team/app:1.2@sha256:...`.
+///
+/// The reference is parsed per the distribution spec, and Harbor additionally requires the
+/// repository to be within a project, so at least two path components are expected.
+///
+/// The client methods named after images, i.e. `get_image_tag`, `delete_image_tag`,
+/// `get_image_manifest` and `copy_image`, take the reference. The other repository, tag and
+/// scan methods take the names, which are `repo_name()` and `tag` of the reference.
+///
+/// ```rust
+/// use harbor_rs::harbor::reference::ImageRef;
+///
+/// let image: ImageRef = "harbor.example.com/team/backend/app:1.2".parse().unwrap();
+/// assert_eq!(image.host.as_deref(), Some("harbor.example.com"));
+/// assert_eq!(image.project, "team");
+/// assert_eq!(image.repo_name(), "team/backend/app");
+/// assert_eq!(image.tag.as_deref(), Some("1.2"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageRef {
+    /// The registry host with an optional port.
+    pub host: Option<String>,
+    pub project: String,
+    /// The repository path within the project, may be nested.
+    pub repository: String,
+    pub tag: Option<String>,
+    pub digest: Option<String>,
+}
+
+impl ImageRef {
+    /// The repository name including the project name, as used by the repository APIs.
+    pub fn repo_name(&self) -> String {
+        format!("{}/{}", self.project, self.repository)
+    }
+
+    /// The image in the `repo:tag` or `repo@digest` form, without the registry host.
+    pub fn source_image(&self) -> Result<String> {
+        if let Some(digest) = &self.digest {
+            Ok(format!("{}@{}", self.repo_name(), digest))
+        } else if let Some(tag) = &self.tag {
+            Ok(format!("{}:{}", self.repo_name(), tag))
+        } else {
+            Err(anyhow!("image reference {} has neither tag nor digest", self))
+        }
+    }
+}
+
+impl FromStr for ImageRef {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (remain, digest) = match s.split_once('@') {
+            Some((remain, digest)) => {
+                validate_digest(digest)?;
+                (remain, Some(digest.to_string()))
+            }
+            None => (s, None),
+        };
+        // the tag separator is a colon after the last slash, otherwise it is the host port
+        let (name, tag) = match remain.rfind(':') {
+            Some(i) if !remain[i..].contains('/') => {
+                let tag = &remain[i + 1..];
+                validate_tag(tag)?;
+                (&remain[..i], Some(tag.to_string()))
+            }
+            _ => (remain, None),
+        };
+        if name.is_empty() {
+            return Err(anyhow!("invalid image reference {}: repository name is empty", s));
+        }
+        if name.len() > NAME_TOTAL_LENGTH_MAX {
+            return Err(anyhow!("invalid image reference {}: repository name must not be more than {} characters", s, NAME_TOTAL_LENGTH_MAX));
+        }
+        let mut components: Vec<&str> = name.split('/').collect();
+        let host = match components.first() {
+            Some(first) if components.len() > 1 && is_host(first) => {
+                validate_host(first)?;
+                Some(components.remove(0).to_string())
+            }
+            _ => None,
+        };
+        for component in &components {
+            validate_path_component(component)?;
+        }
+        if components.len() < 2 {
+            return Err(anyhow!("invalid image reference {}: repository must be within a project", s));
+        }
+        Ok(ImageRef {
+            host,
+            project: components[0].to_string(),
+            repository: components[1..].join("/"),
+            tag,
+            digest,
+        })
+    }
+}
+
+impl fmt::Display for ImageRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(host) = &self.host {
+            write!(f, "{}/", host)?;
+        }
+        write!(f, "{}", self.repo_name())?;
+        if let Some(tag) = &self.tag {
+            write!(f, ":{}", tag)?;
+        }
+        if let Some(digest) = &self.digest {
+            write!(f, "@{}", digest)?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether the first component of the name is a registry host rather than a path component.
+fn is_host(component: &str) -> bool {
+    component.contains('.') || component.contains(':') || component == "localhost"
+        || component.chars().any(|c| c.is_ascii_uppercase())
+}
+
+/// Validate the host, which is dot separated domain components with an optional port.
+fn validate_host(host: &str) -> Result<()> {
+    let (domain, port) = match host.rsplit_once(':') {
+        Some((domain, port)) => (domain, Some(port)),
+        None => (host, None),
+    };
+    if let Some(port) = port {
+        if port.is_empty() || !port.chars().all(|c| c.is_ascii_digit()) {
+            return Err(anyhow!("invalid registry host {}: port must be numeric", host));
+        }
+    }
+    let valid_component = |c: &str| {
+        !c.is_empty()
+            && c.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+            && !c.starts_with('-')
+            && !c.ends_with('-')
+    };
+    if !domain.split('.').all(valid_component) {
+        return Err(anyhow!("invalid registry host {}", host));
+    }
+    Ok(())
+}
+
+/// Validate the path component, which is lowercase alphanumerics separated by
+/// a period, one or two underscores, or one or more dashes.
+fn validate_path_component(component: &str) -> Result<()> {
+    let err = || anyhow!("invalid repository path component {:?}: must be lowercase alphanumerics separated by '.', '_', '__' or dashes", component);
+    let bytes = component.as_bytes();
+    if bytes.is_empty() || !bytes[0].is_ascii_alphanumeric() || !bytes[bytes.len() - 1].is_ascii_alphanumeric() {
+        return Err(err());
+    }
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if b.is_ascii_lowercase() || b.is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < bytes.len() && !(bytes[i].is_ascii_lowercase() || bytes[i].is_ascii_digit()) {
+            i += 1;
+        }
+        let separator = &component[start..i];
+        let valid = separator == "." || separator == "_" || separator == "__" || separator.bytes().all(|c| c == b'-');
+        if !valid {
+            return Err(err());
+        }
+    }
+    Ok(())
+}
+
+/// Validate the tag, which is up to 128 word characters, periods and dashes,
+/// and must not start with a period or a dash.
+fn validate_tag(tag: &str) -> Result<()> {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let valid = !tag.is_empty()
+        && tag.len() <= 128
+        && tag.starts_with(is_word)
+        && tag.chars().all(|c| is_word(c) || c == '.' || c == '-');
+    if !valid {
+        return Err(anyhow!("invalid tag {:?}: must be up to 128 word characters, '.' or '-', and must not start with '.' or '-'", tag));
+    }
+    Ok(())
+}
+
+/// Validate the digest in the `algorithm:hex` form, e.g. `sha256:...`.
+fn validate_digest(digest: &str) -> Result<()> {
+    let (algorithm, hex) = digest
+        .split_once(':')
+        .ok_or_else(|| anyhow!("invalid digest {:?}: must be in the algorithm:hex form", digest))?;
+    let valid_algorithm = algorithm
+        .split(['+', '.', '_', '-'])
+        .all(|c| c.starts_with(|ch: char| ch.is_ascii_alphabetic()) && c.chars().all(|ch| ch.is_ascii_alphanumeric()));
+    if !valid_algorithm {
+        return Err(anyhow!("invalid digest {:?}: invalid algorithm {:?}", digest, algorithm));
+    }
+    let valid_hex = match algorithm {
+        "sha256" => hex.len() == 64 && hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')),
+        "sha512" => hex.len() == 128 && hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')),
+        _ => hex.len() >= 32 && hex.chars().all(|c| c.is_ascii_hexdigit()),
+    };
+    if !valid_hex {
+        return Err(anyhow!("invalid digest {:?}: invalid {} hex", digest, algorithm));
+    }
+    Ok(())
+}
+
+impl Client {
+    /// Check that the registry host of the image, if any, is the host of the client.
+    pub fn check_image_ref(&self, image: &ImageRef) -> Result<()> {
+        let host = match &image.host {
+            Some(host) => host,
+            None => return Ok(()),
+        };
+        let url = Url::parse(self.base_url())?;
+        let (name, port) = match host.rsplit_once(':') {
+            Some((name, port)) => (name, Some(port.parse::<u16>()?)),
+            None => (host.as_str(), None),
+        };
+        let same_name = url.host_str().map(|h| h.eq_ignore_ascii_case(name)).unwrap_or(false);
+        let same_port = match port {
+            Some(port) => url.port_or_known_default() == Some(port),
+            None => url.port().is_none(),
+        };
+        if same_name && same_port {
+            Ok(())
+        } else {
+            Err(anyhow!("registry host {} of image {} does not match {}", host, image, self.base_url()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ImageRef;
+    use crate::Client;
+
+    #[test]
+    fn parse_image_ref() {
+        let digest = "sha256:4c1e997385b8fb4ad4d1d3c7e5af7ff3f882e94d07cf5b78de9e889bc60830e6";
+        let s = format!("harbor.example.com:8443/team/backend/app:1.2@{}", digest);
+        let image: ImageRef = s.parse().unwrap();
+        assert_eq!(image.host.as_deref(), Some("harbor.example.com:8443"));
+        assert_eq!(image.project, "team");
+        assert_eq!(image.repository, "backend/app");
+        assert_eq!(image.tag.as_deref(), Some("1.2"));
+        assert_eq!(image.digest.as_deref(), Some(digest));
+        assert_eq!(image.to_string(), s);
+
+        let image: ImageRef = "library/nginx".parse().unwrap();
+        assert_eq!(image.host, None);
+        assert_eq!(image.repo_name(), "library/nginx");
+        assert_eq!(image.tag, None);
+
+        let invalid = vec!["nginx", "harbor.example.com/nginx", "library/Nginx", "library/nginx:.1", "library/nginx@sha256:abc", "library//nginx", "library/nginx-:1"];
+        for s in invalid {
+            assert!(s.parse::<ImageRef>().is_err(), "{} should be invalid", s);
+        }
+    }
+
+    #[test]
+    fn check_image_ref() {
+        let client = Client::new(String::from("https://harbor.example.com/api"), String::new(), String::new()).unwrap();
+        for s in ["library/nginx:1.0", "harbor.example.com/library/nginx", "Harbor.Example.com:443/library/nginx"] {
+            assert!(client.check_image_ref(&s.parse().unwrap()).is_ok(), "{} should match", s);
+        }
+        for s in ["docker.io/library/nginx", "harbor.example.com:8443/library/nginx"] {
+            assert!(client.check_image_ref(&s.parse().unwrap()).is_err(), "{} should not match", s);
+        }
+    }
+}
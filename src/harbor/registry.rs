@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use super::client::Client;
+use futures::stream::{Stream, TryStreamExt};
+use serde::Deserialize;
+use anyhow::{anyhow, Result};
+use reqwest::header::{HeaderMap, ACCEPT, CONTENT_LENGTH, CONTENT_TYPE, WWW_AUTHENTICATE};
+use reqwest::{Method, Response, StatusCode, Url};
+
+/// The manifest media types accepted by default.
+pub const MANIFEST_MEDIA_TYPES: [&str; 4] = [
+    "application/vnd.docker.distribution.manifest.v2+json",
+    "application/vnd.docker.distribution.manifest.list.v2+json",
+    "application/vnd.oci.image.manifest.v1+json",
+    "application/vnd.oci.image.index.v1+json",
+];
+
+/// The descriptor of a manifest or blob, read from the response headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Descriptor {
+    pub media_type: Option<String>,
+    pub digest: Option<String>,
+    pub size: Option<u64>,
+}
+
+impl Descriptor {
+    fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).map(|v| v.to_string());
+        Descriptor {
+            media_type: header(CONTENT_TYPE.as_str()),
+            digest: header("Docker-Content-Digest"),
+            size: header(CONTENT_LENGTH.as_str()).and_then(|v| v.parse::<u64>().ok()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Manifest {
+    pub descriptor: Descriptor,
+    /// The raw manifest, which is kept as is to keep the digest.
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Catalog {
+    #[serde(default)]
+    repositories: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagList {
+    #[serde(default)]
+    tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Token {
+    token: Option<String>,
+    access_token: Option<String>,
+}
+
+/// The client of the Docker Registry HTTP API v2 embedded in Harbor,
+/// which does the token handshake with the credentials of `Client`.
+///
+/// ```rust,no_run
+/// # async fn run(client: &harbor_rs::Client) -> anyhow::Result<()> {
+/// let registry = client.registry()?;
+/// let tags = registry.list_tags("library/nginx").await?;
+/// if let Some(descriptor) = registry.head_manifest("library/nginx", &tags[0]).await? {
+///     println!("{:?}", descriptor.digest);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct RegistryClient<'a> {
+    client: &'a Client,
+    registry_url: String,
+    /// The tokens by scope.
+    tokens: Mutex<HashMap<String, String>>,
+}
+
+impl Client {
+    /// Get the client of the embedded registry, which is served at `/v2/` of the Harbor host.
+    pub fn registry(&self) -> Result<RegistryClient<'_>> {
+        let mut url = Url::parse(self.base_url())?;
+        url.set_path("");
+        url.set_query(None);
+        Ok(RegistryClient {
+            client: self,
+            registry_url: url.as_str().trim_end_matches('/').to_string(),
+            tokens: Mutex::new(HashMap::new()),
+        })
+    }
+}
+
+impl fmt::Debug for RegistryClient<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let scopes: Vec<String> = self.tokens.lock().map(|t| t.keys().cloned().collect()).unwrap_or_default();
+        f.debug_struct("RegistryClient")
+            .field("registry_url", &self.registry_url)
+            .field("token_scopes", &scopes)
+            .finish()
+    }
+}
+
+impl<'a> RegistryClient<'a> {
+    /// Send the request with the token of the scope, the token is requested on the first 401 response.
+    async fn send(&self, method: Method, path: &str, query: &[(&str, String)], scope: &str, accept: &[&str]) -> Result<Response> {
+        let url = format!("{}/v2/{}", self.registry_url, path);
+        let mut retried = false;
+        loop {
+            let mut req = self.client.client.request(method.clone(), &url).query(query);
+            if !accept.is_empty() {
+                req = req.header(ACCEPT, accept.join(", "));
+            }
+            let token = self.tokens.lock().unwrap().get(scope).cloned();
+            if let Some(token) = token {
+                req = req.bearer_auth(token);
+            }
+            let resp = req.send().await?;
+            if resp.status() != StatusCode::UNAUTHORIZED || retried {
+                return Ok(resp);
+            }
+            let challenge = resp.headers()
+                .get(WWW_AUTHENTICATE)
+                .and_then(|v| v.to_str().ok())
+                .ok_or_else(|| anyhow!("no WWW-Authenticate header in the 401 response"))?;
+            let token = self.fetch_token(challenge, scope).await?;
+            self.tokens.lock().unwrap().insert(scope.to_string(), token);
+            retried = true;
+        }
+    }
+
+    async fn fetch_token(&self, challenge: &str, scope: &str) -> Result<String> {
+        let params = parse_challenge(challenge)?;
+        let realm = params.get("realm").ok_or_else(|| anyhow!("no realm in challenge: {}", challenge))?;
+        let mut query = vec![("scope", scope.to_string())];
+        if let Some(service) = params.get("service") {
+            query.push(("service", service.clone()));
+        }
+        // the basic auth is in the default headers of the client
+        let resp = self.client.client.get(realm).query(&query).send().await?;
+        if !resp.status().eq(&StatusCode::OK) {
+            return Err(anyhow!("failed to get registry token: {}", resp.text().await?));
+        }
+        let token = resp.json::<Token>().await?;
+        token.token.or(token.access_token).ok_or_else(|| anyhow!("no token in the token response"))
+    }
+
+    /// List the repositories, which requires the system admin role.
+    pub async fn catalog(&self, n: Option<u32>, last: Option<&str>) -> Result<Vec<String>> {
+        let mut params = Vec::new();
+        if let Some(n) = n {
+            params.push(("n", n.to_string()));
+        }
+        if let Some(last) = last {
+            params.push(("last", last.to_string()));
+        }
+        let resp = self.send(Method::GET, "_catalog", &params, "registry:catalog:*", &[]).await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Catalog>().await?.repositories)
+        } else {
+            Err(anyhow!("failed to get catalog: {}", resp.text().await?))
+        }
+    }
+
+    /// List the tags of the repository.
+    pub async fn list_tags(&self, repo: &str) -> Result<Vec<String>> {
+        let path = format!("{}/tags/list", repo);
+        let resp = self.send(Method::GET, &path, &[], &pull_scope(repo), &[]).await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<TagList>().await?.tags.unwrap_or_default())
+        } else {
+            Err(anyhow!("failed to list tags: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the manifest by tag or digest, with the accepted media types, `MANIFEST_MEDIA_TYPES` if empty.
+    pub async fn get_manifest(&self, repo: &str, reference: &str, accept: &[&str]) -> Result<Manifest> {
+        let accept = if accept.is_empty() { &MANIFEST_MEDIA_TYPES[..] } else { accept };
+        let path = format!("{}/manifests/{}", repo, reference);
+        let resp = self.send(Method::GET, &path, &[], &pull_scope(repo), accept).await?;
+        if resp.status().eq(&StatusCode::OK) {
+            let descriptor = Descriptor::from_headers(resp.headers());
+            let body = resp.bytes().await?.to_vec();
+            Ok(Manifest { descriptor, body })
+        } else {
+            Err(anyhow!("failed to get manifest: {}", resp.text().await?))
+        }
+    }
+
+    /// Check the manifest by tag or digest, return None if not exist.
+    pub async fn head_manifest(&self, repo: &str, reference: &str) -> Result<Option<Descriptor>> {
+        let path = format!("{}/manifests/{}", repo, reference);
+        let resp = self.send(Method::HEAD, &path, &[], &pull_scope(repo), &MANIFEST_MEDIA_TYPES).await?;
+        match resp.status() {
+            StatusCode::OK => Ok(Some(Descriptor::from_headers(resp.headers()))),
+            StatusCode::NOT_FOUND => Ok(None),
+            status => Err(anyhow!("failed to head manifest: {}", status)),
+        }
+    }
+
+    /// Check the blob, return None if not exist.
+    pub async fn head_blob(&self, repo: &str, digest: &str) -> Result<Option<Descriptor>> {
+        let path = format!("{}/blobs/{}", repo, digest);
+        let resp = self.send(Method::HEAD, &path, &[], &pull_scope(repo), &[]).await?;
+        match resp.status() {
+            StatusCode::OK => Ok(Some(Descriptor::from_headers(resp.headers()))),
+            StatusCode::NOT_FOUND => Ok(None),
+            status => Err(anyhow!("failed to head blob: {}", status)),
+        }
+    }
+
+    /// Stream the content of the blob.
+    pub async fn get_blob(&self, repo: &str, digest: &str) -> Result<impl Stream<Item = Result<bytes::Bytes>>> {
+        let path = format!("{}/blobs/{}", repo, digest);
+        let resp = self.send(Method::GET, &path, &[], &pull_scope(repo), &[]).await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.bytes_stream().map_err(anyhow::Error::from))
+        } else {
+            Err(anyhow!("failed to get blob: {}", resp.text().await?))
+        }
+    }
+}
+
+fn pull_scope(repo: &str) -> String {
+    format!("repository:{}:pull", repo)
+}
+
+/// Parse the params of the `WWW-Authenticate` challenge, e.g.
+/// `Bearer realm="https://harbor/service/token",service="harbor-registry"`.
+fn parse_challenge(challenge: &str) -> Result<HashMap<String, String>> {
+    let params = challenge.strip_prefix("Bearer ")
+        .ok_or_else(|| anyhow!("unsupported challenge: {}", challenge))?;
+    let mut result = HashMap::new();
+    let mut rest = params.trim();
+    while !rest.is_empty() {
+        let (key, value) = rest.split_once('=')
+            .ok_or_else(|| anyhow!("invalid challenge: {}", challenge))?;
+        let value = value.trim_start();
+        let (value, remain) = match value.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"').ok_or_else(|| anyhow!("invalid challenge: {}", challenge))?;
+                (&quoted[..end], &quoted[end + 1..])
+            }
+            None => match value.find(',') {
+                Some(end) => (&value[..end], &value[end..]),
+                None => (value, ""),
+            },
+        };
+        result.insert(key.trim().to_string(), value.to_string());
+        rest = remain.trim_start().trim_start_matches(',').trim_start();
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_challenge;
+    use crate::Client;
+
+    #[test]
+    fn test_parse_challenge() {
+        let params = parse_challenge(r#"Bearer realm="https://harbor.example.com/service/token",service="harbor-registry",scope="repository:library/nginx:pull,push""#).unwrap();
+        assert_eq!(params["realm"], "https://harbor.example.com/service/token");
+        assert_eq!(params["service"], "harbor-registry");
+        assert_eq!(params["scope"], "repository:library/nginx:pull,push");
+        assert!(parse_challenge(r#"Basic realm="harbor""#).is_err());
+    }
+
+    #[test]
+    fn registry_client_debug() {
+        let client = Client::new(String::from("https://harbor.example.com/api"), String::new(), String::new()).unwrap();
+        let registry = client.registry().unwrap();
+        registry.tokens.lock().unwrap().insert(String::from("registry:catalog:*"), String::from("secret-token"));
+        let s = format!("{:?}", registry);
+        assert!(s.contains("registry:catalog:*"));
+        assert!(!s.contains("secret-token"));
+    }
+}
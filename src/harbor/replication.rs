@@ -0,0 +1,422 @@
+use super::client::Client;
+use super::endpoint::Registry;
+use super::gc::validate_cron;
+use super::job::{job_id_from_location, JobHandle, JobKind};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use anyhow::{anyhow, Result};
+use reqwest::{Method, StatusCode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceType {
+    Image,
+    Chart,
+}
+
+/// Whether the tag and label filters select the matched or the not matched resources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterDecoration {
+    #[default]
+    Matches,
+    Excludes,
+}
+
+/// The filter of the resources to replicate, name and tag support doublestar patterns.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "RawReplicationFilter", into = "RawReplicationFilter")]
+pub enum ReplicationFilter {
+    Name(String),
+    Tag { pattern: String, decoration: FilterDecoration },
+    Label { labels: Vec<String>, decoration: FilterDecoration },
+    Resource(ResourceType),
+}
+
+impl ReplicationFilter {
+    /// Select the tags matching the pattern.
+    pub fn tag_matches(pattern: &str) -> Self {
+        ReplicationFilter::Tag { pattern: pattern.to_string(), decoration: FilterDecoration::Matches }
+    }
+
+    /// Select the tags not matching the pattern.
+    pub fn tag_excludes(pattern: &str) -> Self {
+        ReplicationFilter::Tag { pattern: pattern.to_string(), decoration: FilterDecoration::Excludes }
+    }
+
+    /// Select the resources with all the labels.
+    pub fn label_matches(labels: Vec<String>) -> Self {
+        ReplicationFilter::Label { labels, decoration: FilterDecoration::Matches }
+    }
+
+    /// Select the resources without the labels.
+    pub fn label_excludes(labels: Vec<String>) -> Self {
+        ReplicationFilter::Label { labels, decoration: FilterDecoration::Excludes }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct RawReplicationFilter {
+    #[serde(rename = "type")]
+    filter_type: String,
+    value: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    decoration: Option<FilterDecoration>,
+}
+
+impl TryFrom<RawReplicationFilter> for ReplicationFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: RawReplicationFilter) -> Result<Self> {
+        let decoration = raw.decoration.unwrap_or_default();
+        Ok(match raw.filter_type.as_str() {
+            "name" => ReplicationFilter::Name(serde_json::from_value(raw.value)?),
+            "tag" => ReplicationFilter::Tag { pattern: serde_json::from_value(raw.value)?, decoration },
+            "label" => ReplicationFilter::Label { labels: serde_json::from_value(raw.value)?, decoration },
+            "resource" => ReplicationFilter::Resource(serde_json::from_value(raw.value)?),
+            other => return Err(anyhow!("unknown replication filter type {}", other)),
+        })
+    }
+}
+
+impl From<ReplicationFilter> for RawReplicationFilter {
+    fn from(filter: ReplicationFilter) -> Self {
+        let (filter_type, value, decoration) = match filter {
+            ReplicationFilter::Name(name) => ("name", json!(name), None),
+            ReplicationFilter::Tag { pattern, decoration } => ("tag", json!(pattern), Some(decoration)),
+            ReplicationFilter::Label { labels, decoration } => ("label", json!(labels), Some(decoration)),
+            ReplicationFilter::Resource(resource) => ("resource", json!(resource), None),
+        };
+        RawReplicationFilter {
+            filter_type: filter_type.to_string(),
+            value,
+            decoration,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerType {
+    Manual,
+    Scheduled,
+    EventBased,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TriggerSettings {
+    #[serde(default)]
+    pub cron: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReplicationTrigger {
+    #[serde(rename = "type")]
+    pub trigger_type: TriggerType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_settings: Option<TriggerSettings>,
+}
+
+impl ReplicationTrigger {
+    pub fn manual() -> Self {
+        ReplicationTrigger {
+            trigger_type: TriggerType::Manual,
+            trigger_settings: None,
+        }
+    }
+
+    pub fn scheduled(cron: &str) -> Result<Self> {
+        validate_cron(cron)?;
+        Ok(ReplicationTrigger {
+            trigger_type: TriggerType::Scheduled,
+            trigger_settings: Some(TriggerSettings {
+                cron: cron.to_string(),
+            }),
+        })
+    }
+
+    /// Replicate when the resources are pushed or deleted.
+    pub fn event_based() -> Self {
+        ReplicationTrigger {
+            trigger_type: TriggerType::EventBased,
+            trigger_settings: None,
+        }
+    }
+}
+
+/// The replication policy, one of `src_registry` and `dest_registry` must be the local Harbor.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReplicationPolicy {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// The source registry, none for pushing from the local Harbor.
+    pub src_registry: Option<Registry>,
+    /// The destination registry, none for pulling to the local Harbor.
+    pub dest_registry: Option<Registry>,
+    /// The destination namespace, the source namespace is kept if empty.
+    #[serde(default)]
+    pub dest_namespace: String,
+    #[serde(default)]
+    pub filters: Vec<ReplicationFilter>,
+    pub trigger: ReplicationTrigger,
+    /// Whether to replicate the deletion operation.
+    #[serde(default)]
+    pub deletion: bool,
+    /// Whether to override the resources on the destination registry.
+    #[serde(rename = "override", default)]
+    pub override_resources: bool,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creation_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_time: Option<String>,
+}
+
+impl ReplicationPolicy {
+    pub fn new(name: String, src_registry: Option<Registry>, dest_registry: Option<Registry>, trigger: ReplicationTrigger) -> Self {
+        ReplicationPolicy {
+            id: None,
+            name,
+            description: String::new(),
+            src_registry,
+            dest_registry,
+            dest_namespace: String::new(),
+            filters: vec![],
+            trigger,
+            deletion: false,
+            override_resources: true,
+            enabled: true,
+            creation_time: None,
+            update_time: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReplicationExecution {
+    pub id: i64,
+    pub policy_id: i64,
+    /// The status of the execution, e.g. `InProgress`, `Succeed`, `Failed` or `Stopped`.
+    pub status: String,
+    pub status_text: Option<String>,
+    pub trigger: String,
+    pub start_time: String,
+    pub end_time: Option<String>,
+    pub total: i64,
+    pub failed: i64,
+    pub succeed: i64,
+    pub in_progress: i64,
+    pub stopped: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReplicationTask {
+    pub id: i64,
+    pub execution_id: i64,
+    pub resource_type: String,
+    pub src_resource: String,
+    pub dst_resource: String,
+    pub job_id: String,
+    pub status: String,
+    /// The operation of the task, `copy` or `deletion`.
+    pub operation: Option<String>,
+    pub start_time: String,
+    pub end_time: Option<String>,
+}
+
+impl Client {
+    /// List replication policies.
+    pub async fn list_replication_policies(&self, name: Option<String>, page: Option<u32>, page_size: Option<u32>) -> Result<Vec<ReplicationPolicy>> {
+        let path = "/replication/policies";
+        let mut params = Vec::new();
+        if let Some(name) = name {
+            params.push(("name", name));
+        }
+        if let Some(page) = page {
+            params.push(("page", page.to_string()));
+        }
+        if let Some(page_size) = page_size {
+            params.push(("page_size", page_size.to_string()));
+        }
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<ReplicationPolicy>>().await?)
+        } else {
+            Err(anyhow!("failed to list replication policies: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the replication policy.
+    pub async fn get_replication_policy(&self, id: i64) -> Result<ReplicationPolicy> {
+        let path = format!("/replication/policies/{}", id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<ReplicationPolicy>().await?)
+        } else {
+            Err(anyhow!("failed to get replication policy: {}", resp.text().await?))
+        }
+    }
+
+    /// Create a replication policy.
+    pub async fn create_replication_policy(&self, policy: &ReplicationPolicy) -> Result<()> {
+        let path = "/replication/policies";
+        let resp = self.build_request(Method::POST, path).json(policy).send().await?;
+        if resp.status().eq(&StatusCode::CREATED) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to create replication policy: {}", resp.text().await?))
+        }
+    }
+
+    /// Update the replication policy.
+    pub async fn update_replication_policy(&self, id: i64, policy: &ReplicationPolicy) -> Result<()> {
+        let path = format!("/replication/policies/{}", id);
+        let resp = self.build_request(Method::PUT, path).json(policy).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to update replication policy: {}", resp.text().await?))
+        }
+    }
+
+    /// Delete the replication policy.
+    pub async fn delete_replication_policy(&self, id: i64) -> Result<()> {
+        let path = format!("/replication/policies/{}", id);
+        let resp = self.build_request(Method::DELETE, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to delete replication policy: {}", resp.text().await?))
+        }
+    }
+
+    /// Start a replication execution of the policy, and return the handle of the execution.
+    pub async fn start_replication(&self, policy_id: i64) -> Result<JobHandle<'_>> {
+        let last_id = self.list_replication_executions(Some(policy_id), None, Some(1)).await?
+            .first()
+            .map(|e| e.id)
+            .unwrap_or(0);
+        let path = "/replication/executions";
+        let payload = json!({ "policy_id": policy_id });
+        let resp = self.build_request(Method::POST, path).json(&payload).send().await?;
+        if !resp.status().eq(&StatusCode::CREATED) {
+            return Err(anyhow!("failed to start replication: {}", resp.text().await?));
+        }
+        let id = match job_id_from_location(&resp) {
+            Some(id) => id,
+            // the first execution created after the start
+            None => self.list_replication_executions(Some(policy_id), None, Some(100)).await?
+                .iter()
+                .map(|e| e.id)
+                .filter(|id| *id > last_id)
+                .min()
+                .ok_or_else(|| anyhow!("failed to find the started replication execution"))?,
+        };
+        Ok(JobHandle::new(self, JobKind::Replication, id))
+    }
+
+    /// List replication executions, ordered by ID desc.
+    pub async fn list_replication_executions(&self, policy_id: Option<i64>, page: Option<u32>, page_size: Option<u32>) -> Result<Vec<ReplicationExecution>> {
+        let path = "/replication/executions";
+        let mut params = Vec::new();
+        if let Some(policy_id) = policy_id {
+            params.push(("policy_id", policy_id.to_string()));
+        }
+        if let Some(page) = page {
+            params.push(("page", page.to_string()));
+        }
+        if let Some(page_size) = page_size {
+            params.push(("page_size", page_size.to_string()));
+        }
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<ReplicationExecution>>().await?)
+        } else {
+            Err(anyhow!("failed to list replication executions: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the replication execution.
+    pub async fn get_replication_execution(&self, id: i64) -> Result<ReplicationExecution> {
+        let path = format!("/replication/executions/{}", id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<ReplicationExecution>().await?)
+        } else {
+            Err(anyhow!("failed to get replication execution: {}", resp.text().await?))
+        }
+    }
+
+    /// Stop the replication execution.
+    pub async fn stop_replication_execution(&self, id: i64) -> Result<()> {
+        let path = format!("/replication/executions/{}", id);
+        let resp = self.build_request(Method::PUT, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to stop replication execution: {}", resp.text().await?))
+        }
+    }
+
+    /// List the tasks of the replication execution.
+    pub async fn list_replication_tasks(&self, execution_id: i64, page: Option<u32>, page_size: Option<u32>) -> Result<Vec<ReplicationTask>> {
+        let path = format!("/replication/executions/{}/tasks", execution_id);
+        let mut params = Vec::new();
+        if let Some(page) = page {
+            params.push(("page", page.to_string()));
+        }
+        if let Some(page_size) = page_size {
+            params.push(("page_size", page_size.to_string()));
+        }
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<ReplicationTask>>().await?)
+        } else {
+            Err(anyhow!("failed to list replication tasks: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the log of the replication task.
+    pub async fn get_replication_task_log(&self, execution_id: i64, task_id: i64) -> Result<String> {
+        let path = format!("/replication/executions/{}/tasks/{}/log", execution_id, task_id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.text().await?)
+        } else {
+            Err(anyhow!("failed to get replication task log: {}", resp.text().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReplicationFilter, ReplicationPolicy, ReplicationTrigger, ResourceType};
+
+    #[test]
+    fn replication_policy_serde() {
+        let mut policy = ReplicationPolicy::new(String::from("sync"), None, None, ReplicationTrigger::scheduled("0 0 1 * * *").unwrap());
+        policy.filters = vec![
+            ReplicationFilter::Name(String::from("library/**")),
+            ReplicationFilter::label_matches(vec![String::from("release")]),
+            ReplicationFilter::Resource(ResourceType::Image),
+            ReplicationFilter::tag_excludes("v*"),
+        ];
+        let value = serde_json::to_value(&policy).unwrap();
+        assert_eq!(value["trigger"]["type"], "scheduled");
+        assert_eq!(value["override"], true);
+        assert_eq!(value["filters"][0], serde_json::json!({"type": "name", "value": "library/**"}));
+        assert_eq!(value["filters"][2], serde_json::json!({"type": "resource", "value": "image"}));
+        assert_eq!(value["filters"][3], serde_json::json!({"type": "tag", "value": "v*", "decoration": "excludes"}));
+        let parsed: ReplicationPolicy = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.filters, policy.filters);
+        let filter: ReplicationFilter = serde_json::from_str(r#"{"type":"label","value":["rc"],"decoration":"excludes"}"#).unwrap();
+        assert_eq!(filter, ReplicationFilter::label_excludes(vec![String::from("rc")]));
+        let filter: ReplicationFilter = serde_json::from_str(r#"{"type":"tag","value":"v*"}"#).unwrap();
+        assert_eq!(filter, ReplicationFilter::tag_matches("v*"));
+        assert!(ReplicationTrigger::scheduled("* * *").is_err());
+    }
+}
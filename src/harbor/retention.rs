@@ -0,0 +1,433 @@
+use std::collections::BTreeMap;
+use super::client::Client;
+use super::common::{ScopeSelectors, Selector};
+use super::job::{job_id_from_location, JobHandle, JobKind};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use anyhow::{anyhow, Result};
+use reqwest::{Method, StatusCode};
+
+/// The retention rule templates supported by Harbor.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RetentionTemplate {
+    /// Retain the most recently pushed # artifacts.
+    LatestPushedK(u32),
+    /// Retain the most recently pulled # artifacts.
+    LatestPulledN(u32),
+    /// Retain the artifacts pushed within the last # days.
+    NDaysSinceLastPush(u32),
+    /// Retain the artifacts pulled within the last # days.
+    NDaysSinceLastPull(u32),
+    /// Retain always.
+    Always,
+    /// A template unknown to this crate, e.g. `latestActiveK` or `nothing`, kept as is.
+    Other { template: String, params: BTreeMap<String, Value> },
+}
+
+impl RetentionTemplate {
+    fn name(&self) -> &str {
+        match self {
+            RetentionTemplate::LatestPushedK(_) => "latestPushedK",
+            RetentionTemplate::LatestPulledN(_) => "latestPulledN",
+            RetentionTemplate::NDaysSinceLastPush(_) => "nDaysSinceLastPush",
+            RetentionTemplate::NDaysSinceLastPull(_) => "nDaysSinceLastPull",
+            RetentionTemplate::Always => "always",
+            RetentionTemplate::Other { template, .. } => template,
+        }
+    }
+
+    fn params(&self) -> BTreeMap<String, Value> {
+        let mut params = BTreeMap::new();
+        match *self {
+            RetentionTemplate::LatestPushedK(n)
+            | RetentionTemplate::LatestPulledN(n)
+            | RetentionTemplate::NDaysSinceLastPush(n)
+            | RetentionTemplate::NDaysSinceLastPull(n) => {
+                params.insert(self.name().to_string(), json!(n));
+            }
+            RetentionTemplate::Always => {}
+            RetentionTemplate::Other { ref params, .. } => return params.clone(),
+        }
+        params
+    }
+
+    /// Parse the template, the unknown ones or the ones with invalid params are kept in `Other`.
+    fn parse(template: &str, params: &BTreeMap<String, Value>) -> Self {
+        let param = params.get(template)
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32);
+        match (template, param) {
+            ("latestPushedK", Some(n)) => RetentionTemplate::LatestPushedK(n),
+            ("latestPulledN", Some(n)) => RetentionTemplate::LatestPulledN(n),
+            ("nDaysSinceLastPush", Some(n)) => RetentionTemplate::NDaysSinceLastPush(n),
+            ("nDaysSinceLastPull", Some(n)) => RetentionTemplate::NDaysSinceLastPull(n),
+            ("always", _) => RetentionTemplate::Always,
+            _ => RetentionTemplate::Other {
+                template: template.to_string(),
+                params: params.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RawRetentionRule {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<i64>,
+    #[serde(default)]
+    priority: i64,
+    #[serde(default)]
+    disabled: bool,
+    action: String,
+    template: String,
+    #[serde(default)]
+    params: BTreeMap<String, Value>,
+    tag_selectors: Vec<Selector>,
+    scope_selectors: ScopeSelectors,
+}
+
+/// The tag retention rule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawRetentionRule", into = "RawRetentionRule")]
+pub struct RetentionRule {
+    pub id: Option<i64>,
+    pub priority: i64,
+    pub disabled: bool,
+    pub template: RetentionTemplate,
+    pub tag_selectors: Vec<Selector>,
+    pub scope_selectors: ScopeSelectors,
+}
+
+impl RetentionRule {
+    /// Retain the tags selected by `tag_selector` in the repositories selected by `repo_selector`.
+    ///
+    /// ```rust
+    /// use harbor_rs::harbor::common::Selector;
+    /// use harbor_rs::harbor::retention::{RetentionRule, RetentionTemplate};
+    ///
+    /// let rule = RetentionRule::new(RetentionTemplate::LatestPushedK(10), Selector::tag_matches("**"), Selector::repo_matches("**"));
+    /// ```
+    pub fn new(template: RetentionTemplate, tag_selector: Selector, repo_selector: Selector) -> Self {
+        RetentionRule {
+            id: None,
+            priority: 0,
+            disabled: false,
+            template,
+            tag_selectors: vec![tag_selector],
+            scope_selectors: ScopeSelectors {
+                repository: vec![repo_selector],
+            },
+        }
+    }
+}
+
+impl TryFrom<RawRetentionRule> for RetentionRule {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: RawRetentionRule) -> Result<Self> {
+        Ok(RetentionRule {
+            id: raw.id,
+            priority: raw.priority,
+            disabled: raw.disabled,
+            template: RetentionTemplate::parse(&raw.template, &raw.params),
+            tag_selectors: raw.tag_selectors,
+            scope_selectors: raw.scope_selectors,
+        })
+    }
+}
+
+impl From<RetentionRule> for RawRetentionRule {
+    fn from(rule: RetentionRule) -> Self {
+        RawRetentionRule {
+            id: rule.id,
+            priority: rule.priority,
+            disabled: rule.disabled,
+            action: String::from("retain"),
+            template: rule.template.name().to_string(),
+            params: rule.template.params(),
+            tag_selectors: rule.tag_selectors,
+            scope_selectors: rule.scope_selectors,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetentionTriggerSettings {
+    /// The cron of the schedule, empty means no schedule.
+    #[serde(default)]
+    pub cron: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionTrigger {
+    pub kind: String,
+    pub settings: RetentionTriggerSettings,
+}
+
+impl RetentionTrigger {
+    pub fn schedule(cron: &str) -> Self {
+        RetentionTrigger {
+            kind: String::from("Schedule"),
+            settings: RetentionTriggerSettings {
+                cron: cron.to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionScope {
+    pub level: String,
+    #[serde(rename = "ref")]
+    pub reference: i64,
+}
+
+/// The tag retention policy of a project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub algorithm: String,
+    pub rules: Vec<RetentionRule>,
+    pub trigger: RetentionTrigger,
+    pub scope: RetentionScope,
+}
+
+impl RetentionPolicy {
+    /// Create a retention policy of the project, the tags matched by any of the rules are retained.
+    pub fn new(project_id: i64, rules: Vec<RetentionRule>, trigger: RetentionTrigger) -> Self {
+        RetentionPolicy {
+            id: None,
+            algorithm: String::from("or"),
+            rules,
+            trigger,
+            scope: RetentionScope {
+                level: String::from("project"),
+                reference: project_id,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionExecution {
+    pub id: i64,
+    pub policy_id: i64,
+    pub start_time: String,
+    pub end_time: Option<String>,
+    pub status: String,
+    pub trigger: String,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionExecutionTask {
+    pub id: i64,
+    pub execution_id: i64,
+    pub repository: String,
+    pub job_id: String,
+    pub status: String,
+    pub status_code: i64,
+    pub start_time: String,
+    pub end_time: Option<String>,
+    /// The total number of artifacts of the repository.
+    pub total: i64,
+    /// The number of artifacts retained.
+    pub retained: i64,
+}
+
+impl Client {
+    /// Get the retention policy ID of a project, return None if the project has no retention policy.
+    pub async fn get_project_retention_id(&self, project_id: i64) -> Result<Option<i64>> {
+        let project = self.get_project(project_id).await?;
+        let retention_id = project.metadata
+            .as_ref()
+            .and_then(|m| m.get("retention_id"))
+            .and_then(|v| match v {
+                Value::String(s) => s.parse::<i64>().ok(),
+                _ => v.as_i64(),
+            });
+        Ok(retention_id)
+    }
+
+    /// Get retention policy.
+    pub async fn get_retention(&self, id: i64) -> Result<RetentionPolicy> {
+        let path = format!("/retentions/{}", id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<RetentionPolicy>().await?)
+        } else {
+            Err(anyhow!("failed to get retention: {}", resp.text().await?))
+        }
+    }
+
+    /// Create a retention policy.
+    pub async fn create_retention(&self, policy: &RetentionPolicy) -> Result<()> {
+        let path = "/retentions";
+        let resp = self.build_request(Method::POST, path).json(policy).send().await?;
+        if resp.status().eq(&StatusCode::CREATED) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to create retention: {}", resp.text().await?))
+        }
+    }
+
+    /// Update a retention policy.
+    pub async fn update_retention(&self, id: i64, policy: &RetentionPolicy) -> Result<()> {
+        let path = format!("/retentions/{}", id);
+        let resp = self.build_request(Method::PUT, path).json(policy).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to update retention: {}", resp.text().await?))
+        }
+    }
+
+    /// Trigger a retention execution, nothing is deleted if `dry_run` is true.
+    pub async fn trigger_retention_execution(&self, id: i64, dry_run: bool) -> Result<JobHandle<'_>> {
+        let last_id = self.list_retention_executions(id, None, Some(1)).await?
+            .first()
+            .map(|e| e.id)
+            .unwrap_or(0);
+        let path = format!("/retentions/{}/executions", id);
+        let payload = json!({ "dry_run": dry_run });
+        let resp = self.build_request(Method::POST, path).json(&payload).send().await?;
+        if !resp.status().is_success() {
+            return Err(anyhow!("failed to trigger retention execution: {}", resp.text().await?));
+        }
+        let execution_id = match job_id_from_location(&resp) {
+            Some(execution_id) => execution_id,
+            // the first execution created after the trigger
+            None => self.list_retention_executions(id, None, Some(100)).await?
+                .iter()
+                .map(|e| e.id)
+                .filter(|execution_id| *execution_id > last_id)
+                .min()
+                .ok_or_else(|| anyhow!("failed to find the triggered retention execution"))?,
+        };
+        Ok(JobHandle::new(self, JobKind::Retention { retention_id: id }, execution_id))
+    }
+
+    /// Get the executions of a retention policy.
+    pub async fn list_retention_executions(&self, id: i64, page: Option<u32>, page_size: Option<u32>) -> Result<Vec<RetentionExecution>> {
+        let path = format!("/retentions/{}/executions", id);
+        let mut params = Vec::new();
+        if let Some(page) = page {
+            params.push(("page", page.to_string()));
+        }
+        if let Some(page_size) = page_size {
+            params.push(("page_size", page_size.to_string()));
+        }
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<RetentionExecution>>().await?)
+        } else {
+            Err(anyhow!("failed to list retention executions: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the retention execution, Harbor has no endpoint for a single execution,
+    /// so the executions ordered by ID desc are paged through until it is passed.
+    pub async fn get_retention_execution(&self, id: i64, execution_id: i64) -> Result<RetentionExecution> {
+        let page_size = 100;
+        let mut page = 1;
+        loop {
+            let executions = self.list_retention_executions(id, Some(page), Some(page_size)).await?;
+            if let Some(execution) = executions.iter().find(|e| e.id == execution_id) {
+                return Ok(execution.clone());
+            }
+            let passed = executions.iter().any(|e| e.id < execution_id);
+            if passed || (executions.len() as u32) < page_size {
+                return Err(anyhow!("retention execution {} not found", execution_id));
+            }
+            page += 1;
+        }
+    }
+
+    /// Stop a retention execution.
+    pub async fn stop_retention_execution(&self, id: i64, execution_id: i64) -> Result<()> {
+        let path = format!("/retentions/{}/executions/{}", id, execution_id);
+        let payload = json!({ "action": "stop" });
+        let resp = self.build_request(Method::PATCH, path).json(&payload).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to stop retention execution: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the tasks of a retention execution.
+    pub async fn list_retention_tasks(&self, id: i64, execution_id: i64, page: Option<u32>, page_size: Option<u32>) -> Result<Vec<RetentionExecutionTask>> {
+        let path = format!("/retentions/{}/executions/{}/tasks", id, execution_id);
+        let mut params = Vec::new();
+        if let Some(page) = page {
+            params.push(("page", page.to_string()));
+        }
+        if let Some(page_size) = page_size {
+            params.push(("page_size", page_size.to_string()));
+        }
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<RetentionExecutionTask>>().await?)
+        } else {
+            Err(anyhow!("failed to list retention tasks: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the log of a retention task.
+    pub async fn get_retention_task_log(&self, id: i64, execution_id: i64, task_id: i64) -> Result<String> {
+        let path = format!("/retentions/{}/executions/{}/tasks/{}", id, execution_id, task_id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.text().await?)
+        } else {
+            Err(anyhow!("failed to get retention task log: {}", resp.text().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RetentionRule, RetentionTemplate};
+    use crate::harbor::testing;
+    use crate::Client;
+    use crate::harbor::common::Selector;
+
+    #[test]
+    fn retention_rule_serde() {
+        let rule = RetentionRule::new(RetentionTemplate::NDaysSinceLastPush(7), Selector::tag_matches("**"), Selector::repo_matches("**"));
+        let value = serde_json::to_value(&rule).unwrap();
+        assert_eq!(value["action"], "retain");
+        assert_eq!(value["template"], "nDaysSinceLastPush");
+        assert_eq!(value["params"]["nDaysSinceLastPush"], 7);
+        let parsed: RetentionRule = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, rule);
+
+        let mut value = serde_json::to_value(&rule).unwrap();
+        value["template"] = serde_json::json!("latestActiveK");
+        value["params"] = serde_json::json!({"latestActiveK": 3});
+        let parsed: RetentionRule = serde_json::from_value(value.clone()).unwrap();
+        assert!(matches!(&parsed.template, RetentionTemplate::Other { template, .. } if template == "latestActiveK"));
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), value);
+    }
+
+    #[tokio::test]
+    async fn get_retention_execution_paged() {
+        let base_url = testing::serve(|path| {
+            // 250 executions ordered by ID desc
+            let page: i64 = path.split("page=").nth(1)
+                .and_then(|p| p.split('&').next())
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(1);
+            let end = 250 - (page - 1) * 100;
+            let executions: Vec<String> = (1.max(end - 99)..=end).rev()
+                .map(|id| format!(r#"{{"id":{},"policy_id":1,"start_time":"","status":"Succeed","trigger":"MANUAL","dry_run":true}}"#, id))
+                .collect();
+            (200, format!("[{}]", executions.join(",")))
+        }).await;
+        let client = Client::new(base_url, String::new(), String::new()).unwrap();
+        assert_eq!(client.get_retention_execution(1, 120).await.unwrap().id, 120);
+        assert_eq!(client.get_retention_execution(1, 3).await.unwrap().id, 3);
+        assert!(client.get_retention_execution(1, 300).await.is_err());
+    }
+}
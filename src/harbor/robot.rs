@@ -0,0 +1,333 @@
+use std::fmt;
+use super::client::Client;
+use super::permission::{Action, Resource};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use anyhow::{anyhow, Result};
+use reqwest::{Method, StatusCode};
+
+/// The secret of a robot account, which is never printed through `Debug`.
+#[derive(Clone, Deserialize)]
+#[serde(transparent)]
+pub struct RobotSecret(String);
+
+impl RobotSecret {
+    /// Expose the secret, e.g. to save it to the CI variables.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for RobotSecret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RobotSecret(******)")
+    }
+}
+
+/// The robot account just created, the secret is only returned once.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RobotCreated {
+    pub id: Option<i64>,
+    pub name: String,
+    #[serde(alias = "token")]
+    pub secret: RobotSecret,
+    pub expires_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RobotAccess {
+    /// The resource relative to the project, e.g. `repository`,
+    /// `create_project_robot` adds the `/project/{id}/` prefix required by the v1 API.
+    pub resource: Resource,
+    pub action: Action,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effect: Option<String>,
+}
+
+impl RobotAccess {
+    /// ```rust
+    /// use harbor_rs::harbor::permission::{Action, Resource};
+    /// use harbor_rs::harbor::robot::RobotAccess;
+    ///
+    /// let access = RobotAccess::new(Resource::Repository, Action::Push);
+    /// ```
+    pub fn new(resource: Resource, action: Action) -> Self {
+        RobotAccess {
+            resource,
+            action,
+            effect: None,
+        }
+    }
+}
+
+/// The permissions of a system level robot account in a namespace.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RobotPermission {
+    /// The kind of the namespace, e.g. `project`.
+    pub kind: String,
+    /// The namespace, e.g. the project name, or `*` for all projects.
+    pub namespace: String,
+    pub access: Vec<RobotAccess>,
+}
+
+impl RobotPermission {
+    pub fn project(namespace: &str, access: Vec<RobotAccess>) -> Self {
+        RobotPermission {
+            kind: String::from("project"),
+            namespace: namespace.to_string(),
+            access,
+        }
+    }
+}
+
+/// The project level robot account.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProjectRobot {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub project_id: i64,
+    /// The expiration time in unix timestamp, -1 means never expire.
+    pub expires_at: i64,
+    pub disabled: bool,
+    pub creation_time: String,
+    pub update_time: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProjectRobotCreate {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// The expiration time in unix timestamp, the system configuration is used if none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+    pub access: Vec<RobotAccess>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RobotLevel {
+    System,
+    Project,
+}
+
+/// The system level robot account, available since Harbor v2.2.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Robot {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub level: RobotLevel,
+    /// The duration in days, -1 means never expire.
+    pub duration: i64,
+    pub disable: bool,
+    #[serde(default)]
+    pub editable: bool,
+    pub expires_at: i64,
+    pub permissions: Vec<RobotPermission>,
+    pub creation_time: String,
+    pub update_time: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RobotCreate {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub level: RobotLevel,
+    #[serde(default)]
+    pub disable: bool,
+    /// The duration in days, -1 means never expire.
+    pub duration: i64,
+    pub permissions: Vec<RobotPermission>,
+}
+
+impl Client {
+    /// Get all robot accounts of the project.
+    pub async fn list_project_robots(&self, project_id: i64) -> Result<Vec<ProjectRobot>> {
+        let path = format!("/projects/{}/robots", project_id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<ProjectRobot>>().await?)
+        } else {
+            Err(anyhow!("failed to list project robots: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the robot account of the project.
+    pub async fn get_project_robot(&self, project_id: i64, robot_id: i64) -> Result<ProjectRobot> {
+        let path = format!("/projects/{}/robots/{}", project_id, robot_id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<ProjectRobot>().await?)
+        } else {
+            Err(anyhow!("failed to get project robot: {}", resp.text().await?))
+        }
+    }
+
+    /// Create a robot account of the project, and return the token.
+    pub async fn create_project_robot(&self, project_id: i64, robot: &ProjectRobotCreate) -> Result<RobotCreated> {
+        let path = format!("/projects/{}/robots", project_id);
+        let payload = project_robot_payload(project_id, robot)?;
+        let resp = self.build_request(Method::POST, path).json(&payload).send().await?;
+        if resp.status().eq(&StatusCode::CREATED) {
+            Ok(resp.json::<RobotCreated>().await?)
+        } else {
+            Err(anyhow!("failed to create project robot: {}", resp.text().await?))
+        }
+    }
+
+    /// Enable or disable the robot account of the project.
+    pub async fn set_project_robot_disabled(&self, project_id: i64, robot_id: i64, disabled: bool) -> Result<()> {
+        let path = format!("/projects/{}/robots/{}", project_id, robot_id);
+        let payload = json!({ "disabled": disabled });
+        let resp = self.build_request(Method::PUT, path).json(&payload).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to update project robot: {}", resp.text().await?))
+        }
+    }
+
+    /// Delete the robot account of the project.
+    pub async fn delete_project_robot(&self, project_id: i64, robot_id: i64) -> Result<()> {
+        let path = format!("/projects/{}/robots/{}", project_id, robot_id);
+        let resp = self.build_request(Method::DELETE, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to delete project robot: {}", resp.text().await?))
+        }
+    }
+
+    /// List the system level robot accounts.
+    pub async fn list_robots(&self, page: Option<u32>, page_size: Option<u32>) -> Result<Vec<Robot>> {
+        let path = "/robots";
+        let mut params = Vec::new();
+        if let Some(page) = page {
+            params.push(("page", page.to_string()));
+        }
+        if let Some(page_size) = page_size {
+            params.push(("page_size", page_size.to_string()));
+        }
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<Robot>>().await?)
+        } else {
+            Err(anyhow!("failed to list robots: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the system level robot account.
+    pub async fn get_robot(&self, id: i64) -> Result<Robot> {
+        let path = format!("/robots/{}", id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Robot>().await?)
+        } else {
+            Err(anyhow!("failed to get robot: {}", resp.text().await?))
+        }
+    }
+
+    /// Create a system level robot account, and return the secret.
+    pub async fn create_robot(&self, robot: &RobotCreate) -> Result<RobotCreated> {
+        let path = "/robots";
+        let resp = self.build_request(Method::POST, path).json(robot).send().await?;
+        if resp.status().eq(&StatusCode::CREATED) {
+            Ok(resp.json::<RobotCreated>().await?)
+        } else {
+            Err(anyhow!("failed to create robot: {}", resp.text().await?))
+        }
+    }
+
+    /// Update the system level robot account.
+    pub async fn update_robot(&self, id: i64, robot: &Robot) -> Result<()> {
+        let path = format!("/robots/{}", id);
+        let resp = self.build_request(Method::PUT, path).json(robot).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to update robot: {}", resp.text().await?))
+        }
+    }
+
+    /// Enable or disable the system level robot account.
+    pub async fn set_robot_disabled(&self, id: i64, disabled: bool) -> Result<()> {
+        let mut robot = self.get_robot(id).await?;
+        robot.disable = disabled;
+        self.update_robot(id, &robot).await
+    }
+
+    /// Delete the system level robot account.
+    pub async fn delete_robot(&self, id: i64) -> Result<()> {
+        let path = format!("/robots/{}", id);
+        let resp = self.build_request(Method::DELETE, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to delete robot: {}", resp.text().await?))
+        }
+    }
+
+    /// Refresh the secret of the system level robot account, and return the new one.
+    pub async fn refresh_robot_secret(&self, id: i64) -> Result<RobotSecret> {
+        let path = format!("/robots/{}", id);
+        let resp = self.build_request(Method::PATCH, path).json(&json!({})).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            let body = resp.json::<serde_json::Value>().await?;
+            let secret = body.get("secret")
+                .and_then(|s| s.as_str())
+                .ok_or_else(|| anyhow!("failed to refresh robot secret: no secret returned"))?;
+            Ok(RobotSecret(secret.to_string()))
+        } else {
+            Err(anyhow!("failed to refresh robot secret: {}", resp.text().await?))
+        }
+    }
+}
+
+/// The payload of the v1 API, whose resources are prefixed with the project, e.g. `/project/1/repository`.
+fn project_robot_payload(project_id: i64, robot: &ProjectRobotCreate) -> Result<Value> {
+    let mut payload = serde_json::to_value(robot)?;
+    if let Some(access) = payload["access"].as_array_mut() {
+        for item in access {
+            let resource = item["resource"].as_str().unwrap_or_default();
+            item["resource"] = json!(format!("/project/{}/{}", project_id, resource));
+        }
+    }
+    Ok(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{project_robot_payload, ProjectRobotCreate, RobotAccess, RobotCreated, RobotPermission};
+    use crate::harbor::permission::{Action, Resource};
+
+    #[test]
+    fn robot_secret_debug() {
+        let v1: RobotCreated = serde_json::from_str(r#"{"name":"robot$ci","token":"eyJhbGciOi"}"#).unwrap();
+        assert_eq!(v1.secret.expose(), "eyJhbGciOi");
+        let v2: RobotCreated = serde_json::from_str(r#"{"id":1,"name":"robot$ci","secret":"s3cr3t","expires_at":-1}"#).unwrap();
+        assert!(!format!("{:?}", v2).contains("s3cr3t"));
+    }
+
+    #[test]
+    fn robot_access_serde() {
+        let robot = ProjectRobotCreate {
+            name: String::from("ci"),
+            description: String::new(),
+            expires_at: None,
+            access: vec![RobotAccess::new(Resource::Repository, Action::Push)],
+        };
+        let payload = project_robot_payload(1, &robot).unwrap();
+        assert_eq!(payload["access"][0], serde_json::json!({"resource": "/project/1/repository", "action": "push"}));
+
+        let s = r#"{"kind":"project","namespace":"library","access":[{"resource":"artifact","action":"delete"},{"resource":"preheat-policy","action":"create"}]}"#;
+        let permission: RobotPermission = serde_json::from_str(s).unwrap();
+        assert_eq!(permission.access[0], RobotAccess::new(Resource::Artifact, Action::Delete));
+        assert_eq!(permission.access[1].resource, Resource::Other(String::from("preheat-policy")));
+        assert_eq!(serde_json::to_string(&permission).unwrap(), s);
+    }
+}
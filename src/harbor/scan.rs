@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use super::client::Client;
+use super::gc::Schedule;
+use super::job::{job_id_from_location, JobHandle, JobKind, JobStatus};
+use serde::{Deserialize, Serialize};
+use anyhow::{anyhow, Result};
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ScanAllSchedule {
+    pub schedule: Option<Schedule>,
+}
+
+/// The metrics of the scan all job.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScanAllMetrics {
+    /// The total number of scan processes.
+    #[serde(default)]
+    pub total: i64,
+    /// The number of the finished scan processes.
+    #[serde(default)]
+    pub completed: i64,
+    /// The number of scan processes grouped by status, e.g. `Success` or `Error`.
+    #[serde(default)]
+    pub metrics: HashMap<String, i64>,
+    /// The ID of the scan all job.
+    pub requester: Option<String>,
+    /// Whether the scan all job is ongoing.
+    #[serde(default)]
+    pub ongoing: bool,
+    /// The trigger of the scan all job, e.g. `Manual` or `Schedule`.
+    pub trigger: Option<String>,
+}
+
+impl ScanAllMetrics {
+    /// The ID of the scan all job the metrics are of.
+    pub fn requester_id(&self) -> Option<i64> {
+        self.requester.as_ref().and_then(|r| r.parse::<i64>().ok())
+    }
+
+    /// The status of the scan all job `id`, derived from the metrics of the latest scan all job.
+    ///
+    /// Fails if the metrics are replaced by a later scan all job, as the status of `id` is lost.
+    pub fn job_status(&self, id: i64) -> Result<JobStatus> {
+        let requester = self.requester_id().unwrap_or(0);
+        if requester < id {
+            return Ok(JobStatus::Pending);
+        }
+        if requester > id {
+            return Err(anyhow!("the metrics of scan all job {} are replaced by scan all job {}", id, requester));
+        }
+        if self.ongoing {
+            return Ok(JobStatus::Running);
+        }
+        let count = |status: JobStatus| -> i64 {
+            self.metrics.iter()
+                .filter(|(k, _)| JobStatus::parse(k) == status)
+                .map(|(_, v)| *v)
+                .sum()
+        };
+        if count(JobStatus::Failed) > 0 {
+            Ok(JobStatus::Failed)
+        } else if count(JobStatus::Stopped) > 0 {
+            Ok(JobStatus::Stopped)
+        } else {
+            Ok(JobStatus::Succeeded)
+        }
+    }
+}
+
+impl Client {
+    /// Get scan all's schedule.
+    pub async fn get_scan_all_schedule(&self) -> Result<ScanAllSchedule> {
+        let path = "/system/scanAll/schedule";
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<ScanAllSchedule>().await?)
+        } else {
+            Err(anyhow!("failed to get scan all schedule: {}", resp.text().await?))
+        }
+    }
+
+    /// Create a schedule or a manual trigger for the scan all job.
+    pub async fn create_scan_all_schedule(&self, schedule: &Schedule) -> Result<()> {
+        schedule.validate()?;
+        let path = "/system/scanAll/schedule";
+        let payload = json!({"schedule": schedule});
+        let resp = self.build_request(Method::POST, path).json(&payload).send().await?;
+        if resp.status().eq(&StatusCode::CREATED) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to create scan all schedule: {}", resp.text().await?))
+        }
+    }
+
+    /// Update scan all's schedule.
+    pub async fn update_scan_all_schedule(&self, schedule: &Schedule) -> Result<()> {
+        schedule.validate()?;
+        let path = "/system/scanAll/schedule";
+        let payload = json!({"schedule": schedule});
+        let resp = self.build_request(Method::PUT, path).json(&payload).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to update scan all schedule: {}", resp.text().await?))
+        }
+    }
+
+    /// Scan all artifacts immediately, and return the handle of the scan all job.
+    pub async fn trigger_scan_all(&self) -> Result<JobHandle<'_>> {
+        let last_id = self.scan_all_metrics().await?.requester_id().unwrap_or(0);
+        let path = "/system/scanAll/schedule";
+        let payload = json!({"schedule": Schedule::manual()});
+        let resp = self.build_request(Method::POST, path).json(&payload).send().await?;
+        if !resp.status().eq(&StatusCode::CREATED) {
+            return Err(anyhow!("failed to trigger scan all: {}", resp.text().await?));
+        }
+        let id = match job_id_from_location(&resp) {
+            Some(id) => id,
+            // the metrics are of the scan all job created after the trigger
+            None => self.scan_all_metrics().await?
+                .requester_id()
+                .filter(|id| *id > last_id)
+                .ok_or_else(|| anyhow!("failed to find the triggered scan all job"))?,
+        };
+        Ok(JobHandle::new(self, JobKind::ScanAll, id))
+    }
+
+    /// Get the metrics of the latest scan all process.
+    pub async fn scan_all_metrics(&self) -> Result<ScanAllMetrics> {
+        let path = "/scans/all/metrics";
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<ScanAllMetrics>().await?)
+        } else {
+            Err(anyhow!("failed to get scan all metrics: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the metrics of the latest scheduled scan all process.
+    pub async fn scan_schedule_metrics(&self) -> Result<ScanAllMetrics> {
+        let path = "/scans/schedule/metrics";
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<ScanAllMetrics>().await?)
+        } else {
+            Err(anyhow!("failed to get scan schedule metrics: {}", resp.text().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::ScanAllMetrics;
+    use crate::harbor::job::{JobHandle, JobKind, JobStatus};
+    use crate::harbor::testing;
+    use crate::Client;
+
+    #[test]
+    fn scan_all_metrics_status() {
+        let s = r#"{
+            "total": 10,
+            "completed": 10,
+            "metrics": {"Success": 8, "Error": 2},
+            "requester": "7",
+            "ongoing": false,
+            "trigger": "Manual"
+        }"#;
+        let metrics: ScanAllMetrics = serde_json::from_str(s).unwrap();
+        assert_eq!(metrics.requester_id(), Some(7));
+        assert_eq!(metrics.metrics.get("Success"), Some(&8));
+        assert_eq!(metrics.job_status(7).unwrap(), JobStatus::Failed);
+        assert_eq!(metrics.job_status(8).unwrap(), JobStatus::Pending);
+        assert!(metrics.job_status(6).is_err());
+
+        let s = r#"{"total": 3, "completed": 3, "metrics": {"Success": 1, "Stopped": 2}, "requester": "7"}"#;
+        let metrics: ScanAllMetrics = serde_json::from_str(s).unwrap();
+        assert!(!metrics.ongoing);
+        assert_eq!(metrics.job_status(7).unwrap(), JobStatus::Stopped);
+
+        let s = r#"{"total": 3, "completed": 1, "requester": "7", "ongoing": true}"#;
+        let metrics: ScanAllMetrics = serde_json::from_str(s).unwrap();
+        assert_eq!(metrics.job_status(7).unwrap(), JobStatus::Running);
+
+        // the empty metrics of a fresh instance
+        let metrics: ScanAllMetrics = serde_json::from_str("{}").unwrap();
+        assert_eq!((metrics.total, metrics.completed, metrics.ongoing), (0, 0, false));
+        assert_eq!(metrics.requester_id(), None);
+        assert_eq!(metrics.job_status(1).unwrap(), JobStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn wait_replaced_scan_all() {
+        let base_url = testing::serve(|_| (200, String::from(r#"{"requester": "8", "ongoing": true}"#))).await;
+        let client = Client::new(base_url, String::new(), String::new()).unwrap();
+        let job = JobHandle::new(&client, JobKind::ScanAll, 7).with_poll_interval(Duration::from_millis(10));
+        let err = job.wait(Duration::from_secs(5)).await.unwrap_err();
+        assert!(err.to_string().contains("replaced"), "{}", err);
+    }
+}
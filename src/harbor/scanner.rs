@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+use std::fmt;
+use super::client::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use anyhow::{anyhow, Result};
+use reqwest::{Method, StatusCode};
+
+/// The authentication approach of the scanner adapter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ScannerAuth {
+    #[default]
+    #[serde(rename = "")]
+    None,
+    Basic,
+    Bearer,
+    #[serde(rename = "X-ScannerAdapter-API-Key")]
+    ApiKey,
+}
+
+/// The registration of a scanner adapter.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScannerRegistration {
+    pub uuid: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub url: String,
+    #[serde(default)]
+    pub disabled: bool,
+    #[serde(default)]
+    pub is_default: bool,
+    #[serde(default)]
+    pub auth: ScannerAuth,
+    #[serde(rename = "skip_certVerify", default)]
+    pub skip_cert_verify: bool,
+    #[serde(default)]
+    pub use_internal_addr: bool,
+    pub adapter: Option<String>,
+    pub vendor: Option<String>,
+    pub version: Option<String>,
+    /// The health of the scanner, e.g. `healthy` or `unhealthy`.
+    pub health: Option<String>,
+    pub create_time: Option<String>,
+    pub update_time: Option<String>,
+}
+
+/// The request to register or update a scanner adapter.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct ScannerRegistrationReq {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub url: String,
+    #[serde(default)]
+    pub auth: ScannerAuth,
+    /// The credential of the auth, e.g. `username:password` for Basic auth.
+    #[serde(default)]
+    pub access_credential: String,
+    #[serde(rename = "skip_certVerify", default)]
+    pub skip_cert_verify: bool,
+    #[serde(default)]
+    pub use_internal_addr: bool,
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+impl fmt::Debug for ScannerRegistrationReq {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ScannerRegistrationReq")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("url", &self.url)
+            .field("auth", &self.auth)
+            .field("access_credential", &"******")
+            .field("skip_cert_verify", &self.skip_cert_verify)
+            .field("use_internal_addr", &self.use_internal_addr)
+            .field("disabled", &self.disabled)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Scanner {
+    pub name: String,
+    pub vendor: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScannerCapability {
+    pub consumes_mime_types: Vec<String>,
+    pub produces_mime_types: Vec<String>,
+}
+
+/// The metadata of the scanner adapter.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScannerAdapterMetadata {
+    pub scanner: Scanner,
+    #[serde(default)]
+    pub capabilities: Vec<ScannerCapability>,
+    #[serde(default)]
+    pub properties: HashMap<String, String>,
+}
+
+impl Client {
+    /// List the scanner registrations.
+    pub async fn list_scanners(&self) -> Result<Vec<ScannerRegistration>> {
+        let path = "/scanners";
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<ScannerRegistration>>().await?)
+        } else {
+            Err(anyhow!("failed to list scanners: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the scanner registration.
+    pub async fn get_scanner(&self, uuid: &str) -> Result<ScannerRegistration> {
+        let path = format!("/scanners/{}", uuid);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<ScannerRegistration>().await?)
+        } else {
+            Err(anyhow!("failed to get scanner: {}", resp.text().await?))
+        }
+    }
+
+    /// Register a scanner adapter.
+    pub async fn create_scanner(&self, scanner: &ScannerRegistrationReq) -> Result<()> {
+        let path = "/scanners";
+        let resp = self.build_request(Method::POST, path).json(scanner).send().await?;
+        if resp.status().eq(&StatusCode::CREATED) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to create scanner: {}", resp.text().await?))
+        }
+    }
+
+    /// Update the scanner registration.
+    pub async fn update_scanner(&self, uuid: &str, scanner: &ScannerRegistrationReq) -> Result<()> {
+        let path = format!("/scanners/{}", uuid);
+        let resp = self.build_request(Method::PUT, path).json(scanner).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to update scanner: {}", resp.text().await?))
+        }
+    }
+
+    /// Delete the scanner registration.
+    pub async fn delete_scanner(&self, uuid: &str) -> Result<()> {
+        let path = format!("/scanners/{}", uuid);
+        let resp = self.build_request(Method::DELETE, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to delete scanner: {}", resp.text().await?))
+        }
+    }
+
+    /// Set the scanner registration as the system default one.
+    pub async fn set_default_scanner(&self, uuid: &str) -> Result<()> {
+        let path = format!("/scanners/{}", uuid);
+        let payload = json!({ "is_default": true });
+        let resp = self.build_request(Method::PATCH, path).json(&payload).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to set default scanner: {}", resp.text().await?))
+        }
+    }
+
+    /// Check the connectivity of the scanner adapter before registering it.
+    pub async fn ping_scanner(&self, scanner: &ScannerRegistrationReq) -> Result<()> {
+        let path = "/scanners/ping";
+        let resp = self.build_request(Method::POST, path).json(scanner).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to ping scanner: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the metadata of the scanner adapter.
+    pub async fn get_scanner_metadata(&self, uuid: &str) -> Result<ScannerAdapterMetadata> {
+        let path = format!("/scanners/{}/metadata", uuid);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<ScannerAdapterMetadata>().await?)
+        } else {
+            Err(anyhow!("failed to get scanner metadata: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the scanner registration of the project, the system default one if not set.
+    pub async fn get_project_scanner(&self, project_id: i64) -> Result<ScannerRegistration> {
+        let path = format!("/projects/{}/scanner", project_id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<ScannerRegistration>().await?)
+        } else {
+            Err(anyhow!("failed to get project scanner: {}", resp.text().await?))
+        }
+    }
+
+    /// Set the scanner registration of the project.
+    pub async fn set_project_scanner(&self, project_id: i64, uuid: &str) -> Result<()> {
+        let path = format!("/projects/{}/scanner", project_id);
+        let payload = json!({ "uuid": uuid });
+        let resp = self.build_request(Method::PUT, path).json(&payload).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to set project scanner: {}", resp.text().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ScannerAuth, ScannerRegistration, ScannerRegistrationReq};
+
+    #[test]
+    fn scanner_serde() {
+        let req = ScannerRegistrationReq {
+            name: String::from("trivy"),
+            url: String::from("http://trivy:8080"),
+            auth: ScannerAuth::ApiKey,
+            access_credential: String::from("s3cr3t"),
+            ..Default::default()
+        };
+        assert!(!format!("{:?}", req).contains("s3cr3t"));
+        let value = serde_json::to_value(&req).unwrap();
+        assert_eq!(value["auth"], "X-ScannerAdapter-API-Key");
+        assert_eq!(value["skip_certVerify"], false);
+        let scanner: ScannerRegistration = serde_json::from_str(r#"{"uuid":"1","name":"clair","url":"http://clair","auth":"","is_default":true}"#).unwrap();
+        assert_eq!(scanner.auth, ScannerAuth::None);
+    }
+}
@@ -0,0 +1,127 @@
+use super::chart::ChartVersion;
+use super::client::Client;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use anyhow::{anyhow, Result};
+use reqwest::{Method, StatusCode};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SearchProject {
+    pub project_id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub repo_count: i64,
+    #[serde(default)]
+    pub chart_count: i64,
+    pub metadata: Option<Value>,
+}
+
+impl SearchProject {
+    /// Whether the project is public, which is kept as a string in the metadata.
+    pub fn public(&self) -> bool {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get("public"))
+            .map(|v| v == "true" || v == true)
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SearchRepository {
+    /// The name of the repository, including the project name.
+    pub repository_name: String,
+    pub project_id: i64,
+    pub project_name: String,
+    pub project_public: bool,
+    #[serde(default)]
+    pub pull_count: i64,
+    /// The count of tags, or artifacts since Harbor v2.
+    #[serde(default, alias = "artifact_count")]
+    pub tags_count: i64,
+}
+
+/// The chart hit, which is the helm search result serialized without JSON tags.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SearchChart {
+    /// The name of the chart, including the project name.
+    #[serde(alias = "name")]
+    pub name: String,
+    #[serde(alias = "score")]
+    pub score: f64,
+    #[serde(alias = "chart")]
+    pub chart: ChartVersion,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SearchResult {
+    #[serde(default, rename = "project", deserialize_with = "null_as_empty")]
+    pub projects: Vec<SearchProject>,
+    #[serde(default, rename = "repository", deserialize_with = "null_as_empty")]
+    pub repositories: Vec<SearchRepository>,
+    #[serde(default, rename = "chart", deserialize_with = "null_as_empty")]
+    pub charts: Vec<SearchChart>,
+}
+
+/// Harbor responds null instead of an empty list, e.g. the charts when ChartMuseum is disabled.
+fn null_as_empty<'de, D, T>(deserializer: D) -> std::result::Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Ok(Option::<Vec<T>>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+impl Client {
+    /// Search for projects, repositories and helm charts by name.
+    pub async fn search(&self, query: &str) -> Result<SearchResult> {
+        let path = "/search";
+        let params = [("q", query)];
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<SearchResult>().await?)
+        } else {
+            Err(anyhow!("failed to search: {}", resp.text().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SearchResult;
+
+    #[test]
+    fn parse_search_result() {
+        let s = r#"{
+            "project": [{"project_id": 1, "name": "library", "repo_count": 2, "metadata": {"public": "true"}}],
+            "repository": [{"repository_name": "library/nginx", "project_id": 1, "project_name": "library", "project_public": true, "pull_count": 12, "tags_count": 3}],
+            "chart": null
+        }"#;
+        let result: SearchResult = serde_json::from_str(s).unwrap();
+        assert!(result.projects[0].public());
+        assert_eq!(result.repositories[0].tags_count, 3);
+        assert!(result.charts.is_empty());
+
+        let s = r#"{
+            "project": [],
+            "repository": [],
+            "chart": [{
+                "Name": "library/nginx",
+                "Score": 0,
+                "Chart": {
+                    "name": "nginx",
+                    "version": "0.1.0",
+                    "description": "A Helm chart for Kubernetes",
+                    "appVersion": "1.16.0",
+                    "urls": ["library/charts/nginx-0.1.0.tgz"],
+                    "created": "2021-12-20T08:00:00.000000000Z",
+                    "digest": "8a9d2e3b0f1e4d3c2b1a0f9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d9c"
+                }
+            }]
+        }"#;
+        let result: SearchResult = serde_json::from_str(s).unwrap();
+        assert_eq!(result.charts[0].name, "library/nginx");
+        assert_eq!(result.charts[0].chart.metadata.version, "0.1.0");
+    }
+}
@@ -0,0 +1,200 @@
+use super::client::Client;
+use serde::{Deserialize, Serialize};
+use anyhow::{anyhow, Result};
+use reqwest::{Method, StatusCode};
+
+/// The general information of Harbor.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SystemInfo {
+    pub harbor_version: String,
+    /// The auth mode, e.g. `db_auth`, `ldap_auth` or `oidc_auth`.
+    pub auth_mode: Option<String>,
+    pub registry_url: Option<String>,
+    pub external_url: Option<String>,
+    #[serde(default)]
+    pub read_only: bool,
+    #[serde(default)]
+    pub with_notary: bool,
+    #[serde(default)]
+    pub with_chartmuseum: bool,
+    pub project_creation_restriction: Option<String>,
+    pub self_registration: Option<bool>,
+    pub has_ca_root: Option<bool>,
+    pub registry_storage_provider_name: Option<String>,
+    pub notification_enable: Option<bool>,
+}
+
+/// The storage volume of the registry.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StorageVolume {
+    /// Total volume size in bytes.
+    pub total: u64,
+    /// Free volume size in bytes.
+    pub free: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Storage {
+    Single(StorageVolume),
+    Multiple(Vec<StorageVolume>),
+}
+
+#[derive(Debug, Deserialize)]
+struct SystemVolumes {
+    storage: Storage,
+}
+
+/// The health status of a component.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ComponentHealthStatus {
+    /// The component name, e.g. `core`, `database`, `jobservice`, `redis` or `registry`.
+    pub name: String,
+    /// The health status of the component, `healthy` or `unhealthy`.
+    pub status: String,
+    /// The error message when the component is unhealthy.
+    pub error: Option<String>,
+}
+
+/// The health status of Harbor.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HealthStatus {
+    /// The overall health status, `healthy` only if all the components are healthy.
+    pub status: String,
+    #[serde(default)]
+    pub components: Vec<ComponentHealthStatus>,
+}
+
+impl HealthStatus {
+    pub fn is_healthy(&self) -> bool {
+        self.status == "healthy"
+    }
+
+    /// Get the unhealthy components.
+    pub fn unhealthy_components(&self) -> Vec<&ComponentHealthStatus> {
+        self.components.iter().filter(|c| c.status != "healthy").collect()
+    }
+}
+
+/// The statistics of the projects and repositories visible to the current user.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Statistics {
+    pub private_project_count: i64,
+    pub private_repo_count: i64,
+    pub public_project_count: i64,
+    pub public_repo_count: i64,
+    /// Only visible to the system admin.
+    pub total_project_count: Option<i64>,
+    /// Only visible to the system admin.
+    pub total_repo_count: Option<i64>,
+    /// The total storage consumption in bytes, available since Harbor v2.
+    pub total_storage_consumption: Option<u64>,
+}
+
+impl Client {
+    /// Get general system info.
+    pub async fn get_system_info(&self) -> Result<SystemInfo> {
+        let path = "/systeminfo";
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<SystemInfo>().await?)
+        } else {
+            Err(anyhow!("failed to get system info: {}", resp.text().await?))
+        }
+    }
+
+    /// Get system volume info (total/free size).
+    pub async fn get_storage_volumes(&self) -> Result<Vec<StorageVolume>> {
+        let path = "/systeminfo/volumes";
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            match resp.json::<SystemVolumes>().await?.storage {
+                Storage::Single(volume) => Ok(vec![volume]),
+                Storage::Multiple(volumes) => Ok(volumes),
+            }
+        } else {
+            Err(anyhow!("failed to get storage volumes: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the default root certificate.
+    pub async fn get_root_cert(&self) -> Result<Vec<u8>> {
+        let path = "/systeminfo/getcert";
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.bytes().await?.to_vec())
+        } else {
+            Err(anyhow!("failed to get root cert: {}", resp.text().await?))
+        }
+    }
+
+    /// Get the statistics of projects and repositories.
+    pub async fn get_statistics(&self) -> Result<Statistics> {
+        let path = "/statistics";
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Statistics>().await?)
+        } else {
+            Err(anyhow!("failed to get statistics: {}", resp.text().await?))
+        }
+    }
+
+    /// Check the status of Harbor components.
+    pub async fn get_health(&self) -> Result<HealthStatus> {
+        let path = "/health";
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<HealthStatus>().await?)
+        } else {
+            Err(anyhow!("failed to get health: {}", resp.text().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HealthStatus, Statistics, Storage, SystemVolumes};
+
+    #[test]
+    fn parse_storage_volumes() {
+        let v1: SystemVolumes = serde_json::from_str(r#"{"storage":{"total":100,"free":40}}"#).unwrap();
+        assert!(matches!(v1.storage, Storage::Single(ref v) if v.free == 40));
+        let v2: SystemVolumes = serde_json::from_str(r#"{"storage":[{"total":100,"free":40}]}"#).unwrap();
+        assert!(matches!(v2.storage, Storage::Multiple(ref v) if v.len() == 1));
+    }
+
+    #[test]
+    fn parse_health_status() {
+        let s = r#"{"status":"unhealthy","components":[{"name":"core","status":"healthy"},{"name":"redis","status":"unhealthy","error":"timeout"}]}"#;
+        let health: HealthStatus = serde_json::from_str(s).unwrap();
+        assert!(!health.is_healthy());
+        assert_eq!(health.unhealthy_components()[0].name, "redis");
+    }
+
+    #[test]
+    fn parse_statistics() {
+        let admin: Statistics = serde_json::from_str(r#"{
+            "private_project_count": 2,
+            "private_repo_count": 5,
+            "public_project_count": 1,
+            "public_repo_count": 3,
+            "total_project_count": 3,
+            "total_repo_count": 8,
+            "total_storage_consumption": 1073741824
+        }"#).unwrap();
+        assert_eq!(admin.total_project_count, Some(3));
+        assert_eq!(admin.total_repo_count, Some(8));
+        assert_eq!(admin.total_storage_consumption, Some(1073741824));
+
+        let user: Statistics = serde_json::from_str(r#"{
+            "private_project_count": 1,
+            "private_repo_count": 2,
+            "public_project_count": 1,
+            "public_repo_count": 3
+        }"#).unwrap();
+        assert_eq!(user.private_repo_count, 2);
+        assert_eq!(user.total_project_count, None);
+        assert_eq!(user.total_repo_count, None);
+        assert_eq!(user.total_storage_consumption, None);
+    }
+}
@@ -5,6 +5,8 @@ use reqwest::{Method, StatusCode};
 use serde_json::Value;
 use crate::harbor::common::Label;
 use super::common::Signature;
+use super::reference::ImageRef;
+use std::fmt;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Tag {
@@ -26,6 +28,34 @@ pub struct Tag {
     pub os_version: Option<String>,
 }
 
+/// The error returned by `delete_tag` when the tag is protected by an immutable tag rule.
+///
+/// ```rust,no_run
+/// # async fn run(client: &harbor_rs::Client) -> anyhow::Result<()> {
+/// use harbor_rs::harbor::tag::ImmutableTagError;
+///
+/// if let Err(e) = client.delete_tag("library/nginx", "v1.0").await {
+///     if e.downcast_ref::<ImmutableTagError>().is_none() {
+///         return Err(e);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ImmutableTagError {
+    pub repo_name: String,
+    pub tag_name: String,
+}
+
+impl fmt::Display for ImmutableTagError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "tag {}:{} is immutable", self.repo_name, self.tag_name)
+    }
+}
+
+impl std::error::Error for ImmutableTagError {}
+
 impl Client {
     /// Get tags of a relevant repository.
     pub async fn list_tags(&self, repo_name: &str, label_id: Option<&str>, detail: Option<bool>) -> Result<Vec<Tag>> {
@@ -51,8 +81,136 @@ impl Client {
         let resp = self.build_request(Method::DELETE, path).send().await?;
         if resp.status().eq(&reqwest::StatusCode::OK) {
             Ok(())
+        } else if resp.status().eq(&StatusCode::PRECONDITION_FAILED) {
+            Err(ImmutableTagError {
+                repo_name: repo_name.to_string(),
+                tag_name: tag_name.to_string(),
+            }.into())
         } else {
             Err(anyhow!("failed to delete tag: {}", resp.text().await?))
         }
     }
+
+    /// Get the tag the image reference points to.
+    ///
+    /// A digest-only reference fails unless exactly one tag has the digest, and a reference with
+    /// both tag and digest fails if the tag has moved to another digest.
+    pub async fn get_image_tag(&self, image: &ImageRef) -> Result<Tag> {
+        self.check_image_ref(image)?;
+        let repo_name = image.repo_name();
+        let tag = match (&image.tag, &image.digest) {
+            (Some(tag_name), _) => {
+                let path = format!("/repositories/{}/tags/{}", repo_name, tag_name);
+                let resp = self.build_request(Method::GET, path).send().await?;
+                if !resp.status().eq(&StatusCode::OK) {
+                    return Err(anyhow!("failed to get tag: {}", resp.text().await?));
+                }
+                resp.json::<Tag>().await?
+            }
+            (None, Some(digest)) => {
+                let mut tags: Vec<Tag> = self.list_tags(&repo_name, None, None).await?
+                    .into_iter()
+                    .filter(|t| &t.digest == digest)
+                    .collect();
+                if tags.len() > 1 {
+                    let names: Vec<&str> = tags.iter().map(|t| t.name.as_str()).collect();
+                    return Err(anyhow!("digest of {} is shared by tags {}, specify the tag", image, names.join(", ")));
+                }
+                tags.pop().ok_or_else(|| anyhow!("no tag of {} found", image))?
+            }
+            (None, None) => return Err(anyhow!("image reference {} has neither tag nor digest", image)),
+        };
+        if let Some(digest) = &image.digest {
+            if &tag.digest != digest {
+                return Err(anyhow!("tag {} of {} points to {}", tag.name, repo_name, tag.digest));
+            }
+        }
+        Ok(tag)
+    }
+
+    /// Delete the tag the image reference points to, the reference must have a tag since
+    /// a digest may be shared by several tags.
+    pub async fn delete_image_tag(&self, image: &ImageRef) -> Result<()> {
+        if image.tag.is_none() {
+            return Err(anyhow!("image reference {} has no tag to delete", image));
+        }
+        let tag = self.get_image_tag(image).await?;
+        self.delete_tag(&image.repo_name(), &tag.name).await
+    }
+
+    /// Get the manifest and config of the image, `version` is `v2` by default.
+    ///
+    /// The manifest is read through the tag, so a digest-only reference must match exactly one tag.
+    pub async fn get_image_manifest(&self, image: &ImageRef, version: Option<&str>) -> Result<Value> {
+        let tag = self.get_image_tag(image).await?;
+        let path = format!("/repositories/{}/tags/{}/manifest", image.repo_name(), tag.name);
+        let mut params = Vec::new();
+        if let Some(version) = version {
+            params.push(("version", version.to_string()));
+        }
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Value>().await?)
+        } else {
+            Err(anyhow!("failed to get image manifest: {}", resp.text().await?))
+        }
+    }
+
+    /// Copy the source image to the destination, which must have a tag.
+    pub async fn copy_image(&self, src: &ImageRef, dest: &ImageRef, override_existing: bool) -> Result<()> {
+        self.check_image_ref(src)?;
+        self.check_image_ref(dest)?;
+        let tag = dest.tag.as_ref().ok_or_else(|| anyhow!("destination image {} has no tag", dest))?;
+        if dest.digest.is_some() {
+            return Err(anyhow!("destination image {} must not have a digest", dest));
+        }
+        let path = format!("/repositories/{}/tags", dest.repo_name());
+        let payload = serde_json::json!({
+            "tag": tag,
+            "src_image": src.source_image()?,
+            "override": override_existing,
+        });
+        let resp = self.build_request(Method::POST, path).json(&payload).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to copy image: {}", resp.text().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::harbor::testing;
+    use crate::Client;
+
+    const DIGEST: &str = "sha256:4c1e997385b8fb4ad4d1d3c7e5af7ff3f882e94d07cf5b78de9e889bc60830e6";
+
+    fn tag_json(name: &str) -> String {
+        format!(
+            r#"{{"name":"{}","author":"","docker_version":"20.10.7","created":"2021-12-01T00:00:00Z","architecture":"amd64","os":"linux","digest":"{}","size":1024,"signature":null,"labels":[],"push_time":"2021-12-01T00:00:00Z","pull_time":"0001-01-01T00:00:00Z"}}"#,
+            name, DIGEST,
+        )
+    }
+
+    #[tokio::test]
+    async fn get_image_tag_by_digest() {
+        let base_url = testing::serve(|path| {
+            if path.starts_with("/repositories/library/nginx/tags") {
+                (200, format!("[{}]", tag_json("1.21")))
+            } else {
+                (200, format!("[{},{}]", tag_json("1.21"), tag_json("latest")))
+            }
+        }).await;
+        let client = Client::new(base_url, String::new(), String::new()).unwrap();
+
+        let image = format!("library/nginx@{}", DIGEST).parse().unwrap();
+        assert_eq!(client.get_image_tag(&image).await.unwrap().name, "1.21");
+
+        let image = format!("library/redis@{}", DIGEST).parse().unwrap();
+        let err = client.get_image_tag(&image).await.unwrap_err();
+        assert!(err.to_string().contains("shared by tags 1.21, latest"), "{}", err);
+        let err = client.get_image_manifest(&image, None).await.unwrap_err();
+        assert!(err.to_string().contains("shared by tags"), "{}", err);
+    }
 }
@@ -1,35 +1,113 @@
 use super::client::Client;
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
 use reqwest::{Method, StatusCode};
+use std::fmt;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct User {
-    email: String,
-    username: String,
-    realname: String,
-    password: String,
+    pub email: String,
+    pub username: String,
+    pub realname: String,
+    #[serde(default)]
+    pub password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    comment: Option<String>,
+    pub deleted: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    update_time: Option<String>,
+    pub creation_time: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    user_id: Option<i64>,
+    pub role_id: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    deleted: Option<bool>,
+    pub has_admin_role: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    creation_time: Option<String>,
+    pub role_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    role_id: Option<i64>,
+    pub reset_uuid: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    has_admin_role: Option<bool>,
+    pub password_version: Option<String>,
+}
+
+impl fmt::Debug for User {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("User")
+            .field("email", &self.email)
+            .field("username", &self.username)
+            .field("realname", &self.realname)
+            .field("password", &"******")
+            .field("comment", &self.comment)
+            .field("update_time", &self.update_time)
+            .field("user_id", &self.user_id)
+            .field("deleted", &self.deleted)
+            .field("creation_time", &self.creation_time)
+            .field("role_id", &self.role_id)
+            .field("has_admin_role", &self.has_admin_role)
+            .field("role_name", &self.role_name)
+            .field("reset_uuid", &self.reset_uuid.as_ref().map(|_| "******"))
+            .field("password_version", &self.password_version)
+            .finish()
+    }
+}
+
+/// Build a user to create.
+///
+/// ```rust
+/// use harbor_rs::harbor::user::UserBuilder;
+///
+/// let user = UserBuilder::new("testuser", "testuser@gmail.com", "Test1234")
+///     .realname("Test User")
+///     .comment("contractor")
+///     .build();
+/// assert_eq!(user.realname, "Test User");
+/// ```
+#[derive(Debug, Clone)]
+pub struct UserBuilder {
+    user: User,
+}
+
+impl UserBuilder {
+    /// The realname defaults to the username.
+    pub fn new(username: &str, email: &str, password: &str) -> Self {
+        UserBuilder {
+            user: User::new(
+                email.to_string(),
+                username.to_string(),
+                username.to_string(),
+                password.to_string(),
+                None),
+        }
+    }
+
+    pub fn realname(mut self, realname: &str) -> Self {
+        self.user.realname = realname.to_string();
+        self
+    }
+
+    pub fn comment(mut self, comment: &str) -> Self {
+        self.user.comment = Some(comment.to_string());
+        self
+    }
+
+    pub fn build(self) -> User {
+        self.user
+    }
+}
+
+/// The profile fields to update, the unset ones are left unchanged.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UserUpdate {
     #[serde(skip_serializing_if = "Option::is_none")]
-    role_name: Option<String>,
+    pub email: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    reset_uuid: Option<String>,
+    pub realname: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    password_version: Option<String>,
+    pub comment: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,6 +130,10 @@ pub struct UserProfile {
 }
 
 impl User {
+    pub fn builder(username: &str, email: &str, password: &str) -> UserBuilder {
+        UserBuilder::new(username, email, password)
+    }
+
     pub fn new(email: String, username: String, realname: String, password: String, comment: Option<String>) -> Self {
         User {
             email,
@@ -86,6 +168,32 @@ impl User {
     }
 }
 
+/// Validate the password against the password policy of Harbor:
+/// 8-128 characters, with at least one uppercase letter, one lowercase letter and one digit.
+///
+/// ```rust
+/// use harbor_rs::harbor::user::validate_password;
+///
+/// assert!(validate_password("Test1234").is_ok());
+/// assert!(validate_password("test1234").is_err());
+/// ```
+pub fn validate_password(password: &str) -> Result<()> {
+    let len = password.chars().count();
+    if !(8..=128).contains(&len) {
+        return Err(anyhow!("password must be 8-128 characters long, got {}", len));
+    }
+    if !password.chars().any(|c| c.is_ascii_uppercase()) {
+        return Err(anyhow!("password must contain at least one uppercase letter"));
+    }
+    if !password.chars().any(|c| c.is_ascii_lowercase()) {
+        return Err(anyhow!("password must contain at least one lowercase letter"));
+    }
+    if !password.chars().any(|c| c.is_ascii_digit()) {
+        return Err(anyhow!("password must contain at least one digit"));
+    }
+    Ok(())
+}
+
 impl Client {
     /// Get user with user id.
     pub async fn get_user(&self, id: i64) -> Result<User> {
@@ -145,8 +253,14 @@ impl Client {
 
     }
 
-    /// Creates a new user account.
+    /// Creates a new user account, only in the database auth mode.
+    ///
+    /// The users of LDAP have no password in Harbor and are created with `ldap_import_users` instead.
     pub async fn create_user(&self, user: &User) -> Result<()> {
+        if user.password.is_empty() {
+            return Err(anyhow!("password of user {} is required, LDAP users must be imported with ldap_import_users", user.username));
+        }
+        validate_password(&user.password)?;
         let path = "/users";
         let resp = self.build_request(Method::POST, path).json(user).send().await?;
         if resp.status().eq(&StatusCode::CREATED) {
@@ -198,6 +312,21 @@ impl Client {
         }
     }
 
+    /// Update some of the profile fields of a registered user, the others are kept.
+    pub async fn update_user(&self, id: i64, update: &UserUpdate) -> Result<()> {
+        let mut profile = self.get_user(id).await?.get_user_profile();
+        if let Some(email) = &update.email {
+            profile.email = email.clone();
+        }
+        if let Some(realname) = &update.realname {
+            profile.realname = realname.clone();
+        }
+        if let Some(comment) = &update.comment {
+            profile.comment = comment.clone();
+        }
+        self.update_user_profile(id, &profile).await
+    }
+
     /// Update a registered user to change to be an administrator of Harbor.
     pub async fn update_sysadmin(&self, id: i64, has_admin_role: bool) -> Result<()> {
         let path = format!("/users/{}/sysadmin", id);
@@ -211,10 +340,25 @@ impl Client {
     }
 
     /// Change the password on a user that already exists.
+    ///
+    /// Only an administrator can change other's password without the old password,
+    /// use `update_password_with_old` for the self-service change.
     pub async fn update_password(&self, id: i64, new_password: &str) -> Result<()> {
-        let path = format!("/users/{}/password", id);
+        validate_password(new_password)?;
         let body = json!({ "new_password": new_password });
-        let resp = self.build_request(Method::PUT, path).json(&body).send().await.unwrap();
+        self.put_password(id, &body).await
+    }
+
+    /// Change the password of the user, with the old password required by the non-admin self-service change.
+    pub async fn update_password_with_old(&self, id: i64, old_password: &str, new_password: &str) -> Result<()> {
+        validate_password(new_password)?;
+        let body = json!({ "old_password": old_password, "new_password": new_password });
+        self.put_password(id, &body).await
+    }
+
+    async fn put_password(&self, id: i64, body: &Value) -> Result<()> {
+        let path = format!("/users/{}/password", id);
+        let resp = self.build_request(Method::PUT, path).json(body).send().await?;
         if resp.status().eq(&StatusCode::OK) {
             Ok(())
         } else {
@@ -237,6 +381,30 @@ impl Client {
 
 #[cfg(test)]
 mod tests {
+    #[test]
+    fn user_debug() {
+        let user = super::User::builder("testuser", "testuser@gmail.com", "Test1234").build();
+        let s = format!("{:?}", user);
+        assert!(s.contains("testuser@gmail.com"));
+        assert!(!s.contains("Test1234"));
+    }
+
+    #[test]
+    fn validate_password() {
+        assert!(super::validate_password("Test1234").is_ok());
+        let cases = vec![
+            ("Te1", "8-128 characters"),
+            ("test1234", "uppercase"),
+            ("TEST1234", "lowercase"),
+            ("TestTest", "digit"),
+        ];
+        for (password, rule) in cases {
+            let err = super::validate_password(password).unwrap_err();
+            assert!(err.to_string().contains(rule), "{}: {}", password, err);
+        }
+        assert!(super::validate_password(&"Aa1".repeat(43)).is_err());
+    }
+
     #[tokio::test]
     async fn get_current_user() {
         let client = crate::Client::setup().unwrap();
@@ -268,14 +436,14 @@ mod tests {
     async fn list_users() {
         let client = crate::Client::setup().unwrap();
         let users = client.list_users(Some(String::from("testuser")), None, None, None).await.unwrap();
-        assert!(users.len() > 0);
+        assert!(!users.is_empty());
     }
 
     #[tokio::test]
     async fn search_users() {
         let client = crate::Client::setup().unwrap();
         let users = client.search_users("testuser", None, None).await.unwrap();
-        assert!(users.len() > 0);
+        assert!(!users.is_empty());
     }
 
     #[tokio::test]
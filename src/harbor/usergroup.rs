@@ -0,0 +1,166 @@
+use super::client::Client;
+use super::ldap::LdapGroup;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use anyhow::{anyhow, Result};
+use reqwest::{Method, StatusCode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "i64", into = "i64")]
+pub enum GroupType {
+    Ldap,
+    Http,
+    Oidc,
+}
+
+impl TryFrom<i64> for GroupType {
+    type Error = anyhow::Error;
+
+    fn try_from(group_type: i64) -> Result<Self> {
+        match group_type {
+            1 => Ok(GroupType::Ldap),
+            2 => Ok(GroupType::Http),
+            3 => Ok(GroupType::Oidc),
+            _ => Err(anyhow!("unknown group type: {}", group_type)),
+        }
+    }
+}
+
+impl From<GroupType> for i64 {
+    fn from(group_type: GroupType) -> Self {
+        match group_type {
+            GroupType::Ldap => 1,
+            GroupType::Http => 2,
+            GroupType::Oidc => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UserGroup {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub group_name: String,
+    pub group_type: GroupType,
+    /// The DN of the LDAP group, only for the LDAP group type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ldap_group_dn: Option<String>,
+}
+
+impl UserGroup {
+    pub fn ldap(group_name: String, ldap_group_dn: String) -> Self {
+        UserGroup {
+            id: None,
+            group_name,
+            group_type: GroupType::Ldap,
+            ldap_group_dn: Some(ldap_group_dn),
+        }
+    }
+
+    pub fn http(group_name: String) -> Self {
+        UserGroup {
+            id: None,
+            group_name,
+            group_type: GroupType::Http,
+            ldap_group_dn: None,
+        }
+    }
+
+    pub fn oidc(group_name: String) -> Self {
+        UserGroup {
+            id: None,
+            group_name,
+            group_type: GroupType::Oidc,
+            ldap_group_dn: None,
+        }
+    }
+}
+
+impl From<LdapGroup> for UserGroup {
+    fn from(group: LdapGroup) -> Self {
+        UserGroup::ldap(group.group_name, group.ldap_group_dn)
+    }
+}
+
+impl Client {
+    /// Get all user groups information.
+    pub async fn list_user_groups(&self, group_name: Option<String>, page: Option<u32>, page_size: Option<u32>) -> Result<Vec<UserGroup>> {
+        let path = "/usergroups";
+        let mut params = Vec::new();
+        if let Some(group_name) = group_name {
+            params.push(("group_name", group_name));
+        }
+        if let Some(page) = page {
+            params.push(("page", page.to_string()));
+        }
+        if let Some(page_size) = page_size {
+            params.push(("page_size", page_size.to_string()));
+        }
+        let resp = self.build_request(Method::GET, path).query(&params).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<Vec<UserGroup>>().await?)
+        } else {
+            Err(anyhow!("failed to list user groups: {}", resp.text().await?))
+        }
+    }
+
+    /// Get user group information.
+    pub async fn get_user_group(&self, id: i64) -> Result<UserGroup> {
+        let path = format!("/usergroups/{}", id);
+        let resp = self.build_request(Method::GET, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(resp.json::<UserGroup>().await?)
+        } else {
+            Err(anyhow!("failed to get user group: {}", resp.text().await?))
+        }
+    }
+
+    /// Create user group, the LDAP group DN is required by the LDAP group type.
+    pub async fn create_user_group(&self, group: &UserGroup) -> Result<()> {
+        let path = "/usergroups";
+        let resp = self.build_request(Method::POST, path).json(group).send().await?;
+        if resp.status().eq(&StatusCode::CREATED) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to create user group: {}", resp.text().await?))
+        }
+    }
+
+    /// Update the name of a user group.
+    pub async fn update_user_group(&self, id: i64, group_name: &str) -> Result<()> {
+        let path = format!("/usergroups/{}", id);
+        let payload = json!({ "group_name": group_name });
+        let resp = self.build_request(Method::PUT, path).json(&payload).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to update user group: {}", resp.text().await?))
+        }
+    }
+
+    /// Delete user group.
+    pub async fn delete_user_group(&self, id: i64) -> Result<()> {
+        let path = format!("/usergroups/{}", id);
+        let resp = self.build_request(Method::DELETE, path).send().await?;
+        if resp.status().eq(&StatusCode::OK) {
+            Ok(())
+        } else {
+            Err(anyhow!("failed to delete user group: {}", resp.text().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GroupType, UserGroup};
+
+    #[test]
+    fn user_group_serde() {
+        let group = UserGroup::ldap(String::from("dev"), String::from("cn=dev,dc=example,dc=com"));
+        let s = serde_json::to_string(&group).unwrap();
+        assert_eq!(s, r#"{"group_name":"dev","group_type":1,"ldap_group_dn":"cn=dev,dc=example,dc=com"}"#);
+        let group: UserGroup = serde_json::from_str(r#"{"id":2,"group_name":"ops","group_type":3}"#).unwrap();
+        assert_eq!(group.group_type, GroupType::Oidc);
+        assert!(serde_json::from_str::<UserGroup>(r#"{"group_name":"x","group_type":9}"#).is_err());
+    }
+}